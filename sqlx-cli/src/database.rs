@@ -3,6 +3,7 @@ use crate::opt::ConnectOpts;
 use console::style;
 use promptly::{prompt, ReadlineError};
 use sqlx::any::Any;
+use sqlx::config::migrate::Config as MigrateConfig;
 use sqlx::migrate::MigrateDatabase;
 
 pub async fn create(connect_opts: &ConnectOpts) -> anyhow::Result<()> {
@@ -45,17 +46,22 @@ pub async fn drop(connect_opts: &ConnectOpts, confirm: bool, force: bool) -> any
 
 pub async fn reset(
     migration_source: &str,
+    config: &MigrateConfig,
     connect_opts: &ConnectOpts,
     confirm: bool,
     force: bool,
 ) -> anyhow::Result<()> {
     drop(connect_opts, confirm, force).await?;
-    setup(migration_source, connect_opts).await
+    setup(migration_source, config, connect_opts).await
 }
 
-pub async fn setup(migration_source: &str, connect_opts: &ConnectOpts) -> anyhow::Result<()> {
+pub async fn setup(
+    migration_source: &str,
+    config: &MigrateConfig,
+    connect_opts: &ConnectOpts,
+) -> anyhow::Result<()> {
     create(connect_opts).await?;
-    migrate::run(migration_source, connect_opts, false, false, None).await
+    migrate::run(migration_source, config, connect_opts, false, false, None).await
 }
 
 fn ask_to_continue_drop(db_url: &str) -> bool {
@@ -22,52 +22,70 @@ pub use crate::opt::Opt;
 
 pub async fn run(opt: Opt) -> Result<()> {
     match opt.command {
-        Command::Migrate(migrate) => match migrate.command {
-            MigrateCommand::Add {
-                source,
-                description,
-                reversible,
-                sequential,
-                timestamp,
-            } => migrate::add(&source, &description, reversible, sequential, timestamp).await?,
-            MigrateCommand::Run {
-                source,
-                dry_run,
-                ignore_missing,
-                connect_opts,
-                target_version,
-            } => {
-                migrate::run(
-                    &source,
-                    &connect_opts,
+        Command::Migrate(migrate) => {
+            let config = migrate::config()?;
+
+            match migrate.command {
+                MigrateCommand::Add {
+                    source,
+                    description,
+                    reversible,
+                    sequential,
+                    timestamp,
+                } => {
+                    migrate::add(
+                        source.resolve(&config)?,
+                        &config,
+                        &description,
+                        reversible,
+                        sequential,
+                        timestamp,
+                    )
+                    .await?
+                }
+                MigrateCommand::Run {
+                    source,
                     dry_run,
-                    *ignore_missing,
+                    ignore_missing,
+                    connect_opts,
                     target_version,
-                )
-                .await?
-            }
-            MigrateCommand::Revert {
-                source,
-                dry_run,
-                ignore_missing,
-                connect_opts,
-                target_version,
-            } => {
-                migrate::revert(
-                    &source,
-                    &connect_opts,
+                } => {
+                    migrate::run(
+                        source.resolve(&config)?,
+                        &config,
+                        &connect_opts,
+                        dry_run,
+                        *ignore_missing,
+                        target_version,
+                    )
+                    .await?
+                }
+                MigrateCommand::Revert {
+                    source,
                     dry_run,
-                    *ignore_missing,
+                    ignore_missing,
+                    connect_opts,
                     target_version,
-                )
-                .await?
+                } => {
+                    migrate::revert(
+                        source.resolve(&config)?,
+                        &config,
+                        &connect_opts,
+                        dry_run,
+                        *ignore_missing,
+                        target_version,
+                    )
+                    .await?
+                }
+                MigrateCommand::Info {
+                    source,
+                    connect_opts,
+                } => migrate::info(source.resolve(&config)?, &config, &connect_opts).await?,
+                MigrateCommand::BuildScript { source, force } => {
+                    migrate::build_script(source.resolve(&config)?, force)?
+                }
             }
-            MigrateCommand::Info {
-                source,
-                connect_opts,
-            } => migrate::info(&source, &connect_opts).await?,
-            MigrateCommand::BuildScript { source, force } => migrate::build_script(&source, force)?,
-        },
+        }
 
         Command::Database(database) => match database.command {
             DatabaseCommand::Create { connect_opts } => database::create(&connect_opts).await?,
@@ -81,11 +99,26 @@ pub async fn run(opt: Opt) -> Result<()> {
                 source,
                 connect_opts,
                 force,
-            } => database::reset(&source, &connect_opts, !confirmation.yes, force).await?,
+            } => {
+                let config = migrate::config()?;
+
+                database::reset(
+                    source.resolve(&config)?,
+                    &config,
+                    &connect_opts,
+                    !confirmation.yes,
+                    force,
+                )
+                .await?
+            }
             DatabaseCommand::Setup {
                 source,
                 connect_opts,
-            } => database::setup(&source, &connect_opts).await?,
+            } => {
+                let config = migrate::config()?;
+
+                database::setup(source.resolve(&config)?, &config, &connect_opts).await?
+            }
         },
 
         Command::Prepare {
@@ -2,6 +2,8 @@ use crate::opt::ConnectOpts;
 use anyhow::{bail, Context};
 use chrono::Utc;
 use console::style;
+use sqlx::config::migrate::Config as MigrateConfig;
+use sqlx::config::Config;
 use sqlx::migrate::{AppliedMigration, Migrate, MigrateError, MigrationType, Migrator};
 use sqlx::Connection;
 use std::borrow::Cow;
@@ -11,6 +13,40 @@ use std::fs::{self, File};
 use std::path::Path;
 use std::time::Duration;
 
+/// The `[migrate]` section of `sqlx.toml` in the current directory, or the defaults if there is
+/// no such file.
+///
+/// This is the directory `--source` is relative to, so like `migrate!()`, the file is expected
+/// in the root of the crate.
+pub fn config() -> anyhow::Result<MigrateConfig> {
+    match Config::read_from_path("sqlx.toml") {
+        Ok(config) => Ok(config.migrate),
+        Err(e) if e.is_missing() => Ok(MigrateConfig::default()),
+        Err(e) => Err(e).context("Unable to read sqlx.toml"),
+    }
+}
+
+/// The migrations directory to use if `--source` isn't given.
+pub fn default_source(config: &MigrateConfig) -> anyhow::Result<&str> {
+    match &config.migrations_dir {
+        Some(dir) => dir.to_str().with_context(|| {
+            format!("`migrate.migrations_dir` in sqlx.toml is not valid UTF-8: {dir:?}")
+        }),
+        None => Ok("migrations"),
+    }
+}
+
+/// The migrations in `migration_source`, recorded in the table set in `config`.
+async fn migrator(migration_source: &str, config: &MigrateConfig) -> anyhow::Result<Migrator> {
+    let mut migrator = Migrator::new(Path::new(migration_source)).await?;
+
+    if let Some(table_name) = &config.table_name {
+        migrator.set_table_name(table_name.clone());
+    }
+
+    Ok(migrator)
+}
+
 fn create_file(
     migration_source: &str,
     file_prefix: &str,
@@ -102,6 +138,7 @@ impl MigrationOrdering {
 
 pub async fn add(
     migration_source: &str,
+    config: &MigrateConfig,
     description: &str,
     reversible: bool,
     sequential: bool,
@@ -145,7 +182,8 @@ pub async fn add(
         .unwrap_or(false);
 
     if !has_existing_migrations {
-        let quoted_source = if migration_source != "migrations" {
+        // `migrate!()` also defaults to `migrate.migrations_dir`
+        let quoted_source = if migration_source != default_source(config)? {
             format!("{migration_source:?}")
         } else {
             "".to_string()
@@ -192,14 +230,18 @@ fn short_checksum(checksum: &[u8]) -> String {
     s
 }
 
-pub async fn info(migration_source: &str, connect_opts: &ConnectOpts) -> anyhow::Result<()> {
-    let migrator = Migrator::new(Path::new(migration_source)).await?;
+pub async fn info(
+    migration_source: &str,
+    config: &MigrateConfig,
+    connect_opts: &ConnectOpts,
+) -> anyhow::Result<()> {
+    let migrator = migrator(migration_source, config).await?;
     let mut conn = crate::connect(connect_opts).await?;
 
-    conn.ensure_migrations_table().await?;
+    conn.ensure_migrations_table(migrator.table_name()).await?;
 
     let applied_migrations: HashMap<_, _> = conn
-        .list_applied_migrations()
+        .list_applied_migrations(migrator.table_name())
         .await?
         .into_iter()
         .map(|m| (m.version, m))
@@ -273,12 +315,13 @@ fn validate_applied_migrations(
 
 pub async fn run(
     migration_source: &str,
+    config: &MigrateConfig,
     connect_opts: &ConnectOpts,
     dry_run: bool,
     ignore_missing: bool,
     target_version: Option<i64>,
 ) -> anyhow::Result<()> {
-    let migrator = Migrator::new(Path::new(migration_source)).await?;
+    let migrator = migrator(migration_source, config).await?;
     if let Some(target_version) = target_version {
         if !migrator.version_exists(target_version) {
             bail!(MigrateError::VersionNotPresent(target_version));
@@ -287,14 +330,14 @@ pub async fn run(
 
     let mut conn = crate::connect(connect_opts).await?;
 
-    conn.ensure_migrations_table().await?;
+    conn.ensure_migrations_table(migrator.table_name()).await?;
 
-    let version = conn.dirty_version().await?;
+    let version = conn.dirty_version(migrator.table_name()).await?;
     if let Some(version) = version {
         bail!(MigrateError::Dirty(version));
     }
 
-    let applied_migrations = conn.list_applied_migrations().await?;
+    let applied_migrations = conn.list_applied_migrations(migrator.table_name()).await?;
     validate_applied_migrations(&applied_migrations, &migrator, ignore_missing)?;
 
     let latest_version = applied_migrations
@@ -332,7 +375,7 @@ pub async fn run(
                 let elapsed = if dry_run || skip {
                     Duration::new(0, 0)
                 } else {
-                    conn.apply(migration).await?
+                    conn.apply(migrator.table_name(), migration).await?
                 };
                 let text = if skip {
                     "Skipped"
@@ -366,12 +409,13 @@ pub async fn run(
 
 pub async fn revert(
     migration_source: &str,
+    config: &MigrateConfig,
     connect_opts: &ConnectOpts,
     dry_run: bool,
     ignore_missing: bool,
     target_version: Option<i64>,
 ) -> anyhow::Result<()> {
-    let migrator = Migrator::new(Path::new(migration_source)).await?;
+    let migrator = migrator(migration_source, config).await?;
     if let Some(target_version) = target_version {
         if target_version != 0 && !migrator.version_exists(target_version) {
             bail!(MigrateError::VersionNotPresent(target_version));
@@ -380,14 +424,14 @@ pub async fn revert(
 
     let mut conn = crate::connect(connect_opts).await?;
 
-    conn.ensure_migrations_table().await?;
+    conn.ensure_migrations_table(migrator.table_name()).await?;
 
-    let version = conn.dirty_version().await?;
+    let version = conn.dirty_version(migrator.table_name()).await?;
     if let Some(version) = version {
         bail!(MigrateError::Dirty(version));
     }
 
-    let applied_migrations = conn.list_applied_migrations().await?;
+    let applied_migrations = conn.list_applied_migrations(migrator.table_name()).await?;
     validate_applied_migrations(&applied_migrations, &migrator, ignore_missing)?;
 
     let latest_version = applied_migrations
@@ -421,7 +465,7 @@ pub async fn revert(
             let elapsed = if dry_run || skip {
                 Duration::new(0, 0)
             } else {
-                conn.revert(migration).await?
+                conn.revert(migrator.table_name(), migration).await?
             };
             let text = if skip {
                 "Skipped"
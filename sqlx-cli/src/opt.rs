@@ -3,6 +3,7 @@ use std::ops::{Deref, Not};
 use clap::{Args, Parser};
 #[cfg(feature = "completions")]
 use clap_complete::Shell;
+use sqlx::config::migrate::Config as MigrateConfig;
 
 #[derive(Parser, Debug)]
 #[clap(version, about, author)]
@@ -226,15 +227,19 @@ pub enum MigrateCommand {
 #[derive(Args, Debug)]
 pub struct Source {
     /// Path to folder containing migrations.
-    #[clap(long, default_value = "migrations")]
-    source: String,
+    ///
+    /// Defaults to `migrate.migrations_dir` in `sqlx.toml`, or `migrations` if that isn't set.
+    #[clap(long)]
+    source: Option<String>,
 }
 
-impl Deref for Source {
-    type Target = String;
-
-    fn deref(&self) -> &Self::Target {
-        &self.source
+impl Source {
+    /// The `--source` argument, or the default from `config` if it wasn't given.
+    pub fn resolve<'a>(&'a self, config: &'a MigrateConfig) -> anyhow::Result<&'a str> {
+        match &self.source {
+            Some(source) => Ok(source),
+            None => crate::migrate::default_source(config),
+        }
     }
 }
 
@@ -2,6 +2,7 @@ use std::collections::{BTreeSet, HashSet};
 use std::env;
 use std::ffi::{OsStr, OsString};
 use std::fs;
+use std::io::Read as _;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -161,7 +162,7 @@ fn run_prepare_step(ctx: &PrepareCtx, cache_dir: &Path) -> anyhow::Result<()> {
         cache_dir
     ))?;
 
-    // Only delete sqlx-*.json files to avoid accidentally deleting any user data.
+    // Only delete query data files to avoid accidentally deleting any user data.
     for query_file in glob_query_files(cache_dir).context("Failed to read query cache files")? {
         fs::remove_file(&query_file)
             .with_context(|| format!("Failed to delete query file: {}", query_file.display()))?;
@@ -179,7 +180,9 @@ fn run_prepare_step(ctx: &PrepareCtx, cache_dir: &Path) -> anyhow::Result<()> {
             .args(&ctx.cargo_args)
             .env("SQLX_TMP", tmp_dir)
             .env("SQLX_OFFLINE", "false")
-            .env("SQLX_OFFLINE_DIR", cache_dir);
+            .env("SQLX_OFFLINE_FALLBACK", "false")
+            .env("SQLX_OFFLINE_DIR", cache_dir)
+            .env_remove("SQLX_NO_SAVE");
 
         if let Some(database_url) = &ctx.connect_opts.database_url {
             check_command.env("DATABASE_URL", database_url);
@@ -337,25 +340,43 @@ fn minimal_project_recompile_action(metadata: &Metadata, all: bool) -> ProjectRe
     }
 }
 
-/// Find all `query-*.json` files in a directory.
+/// Find all `query-*.json` and `query-*.json.gz` files in a directory, and `queries.json`.
 fn glob_query_files(path: impl AsRef<Path>) -> anyhow::Result<Vec<PathBuf>> {
     let path = path.as_ref();
-    let pattern = path.join("query-*.json");
-    glob::glob(
-        pattern
-            .to_str()
-            .context("query cache path is invalid UTF-8")?,
-    )
-    .with_context(|| format!("failed to read query cache path: {}", path.display()))?
-    .collect::<Result<Vec<_>, _>>()
-    .context("glob failed")
+
+    let mut files = Vec::new();
+
+    for pattern in ["query-*.json", "query-*.json.gz", "queries.json"] {
+        let pattern = path.join(pattern);
+        files.extend(
+            glob::glob(
+                pattern
+                    .to_str()
+                    .context("query cache path is invalid UTF-8")?,
+            )
+            .with_context(|| format!("failed to read query cache path: {}", path.display()))?
+            .collect::<Result<Vec<_>, _>>()
+            .context("glob failed")?,
+        );
+    }
+
+    Ok(files)
 }
 
-/// Load the JSON contents of a query data file.
+/// Load the JSON contents of a query data file, decompressing it if it's gzipped.
 fn load_json_file(path: impl AsRef<Path>) -> anyhow::Result<serde_json::Value> {
     let path = path.as_ref();
-    let file_bytes =
+    let mut file_bytes =
         fs::read(path).with_context(|| format!("failed to load file: {}", path.display()))?;
+
+    if path.extension().map_or(false, |ext| ext == "gz") {
+        let mut decompressed = Vec::new();
+        flate2::read::GzDecoder::new(&file_bytes[..])
+            .read_to_end(&mut decompressed)
+            .with_context(|| format!("failed to decompress file: {}", path.display()))?;
+        file_bytes = decompressed;
+    }
+
     Ok(serde_json::from_slice(&file_bytes)?)
 }
 
@@ -1,6 +1,9 @@
 mod common;
 
+use assert_cmd::Command;
 use common::TestDatabase;
+use sqlx::{migrate::Migrate, Connection, SqliteConnection};
+use std::fs;
 
 #[tokio::test]
 async fn run_reversible_migrations() {
@@ -145,3 +148,65 @@ async fn revert_migrations() {
         assert_eq!(db.applied_migrations().await, vec![] as Vec<i64>);
     }
 }
+
+#[tokio::test]
+async fn run_migrations_with_config() {
+    let dir = tempfile::tempdir().unwrap();
+
+    fs::create_dir(dir.path().join("db")).unwrap();
+    fs::write(
+        dir.path().join("db/20230101000000_test1.sql"),
+        "CREATE TABLE test1 (id INTEGER PRIMARY KEY);",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("sqlx.toml"),
+        "[migrate]\nmigrations_dir = \"db\"\ntable_name = \"custom_migrations\"\n",
+    )
+    .unwrap();
+
+    let url = format!("sqlite://{}", dir.path().join("test.db").display());
+
+    let cargo_sqlx = |args: &[&str]| {
+        Command::cargo_bin("cargo-sqlx")
+            .unwrap()
+            .current_dir(dir.path())
+            .arg("sqlx")
+            .args(args)
+            .args(["--database-url", &url])
+            .assert()
+    };
+
+    cargo_sqlx(&["database", "create"]).success();
+
+    // without `--source`, the migrations are read from `migrate.migrations_dir`
+    cargo_sqlx(&["migrate", "run"]).success();
+
+    let info = cargo_sqlx(&["migrate", "info"]).success();
+    let stdout = String::from_utf8_lossy(&info.get_output().stdout).into_owned();
+    assert!(stdout.contains("20230101000000/installed"), "{stdout}");
+
+    let mut conn = SqliteConnection::connect(&url).await.unwrap();
+
+    let applied = conn
+        .list_applied_migrations("custom_migrations")
+        .await
+        .unwrap();
+    assert_eq!(
+        applied.iter().map(|m| m.version).collect::<Vec<_>>(),
+        [20230101000000]
+    );
+
+    let default_table: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM sqlite_master WHERE name = '_sqlx_migrations'",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap();
+    assert_eq!(default_table, 0);
+
+    conn.close().await.unwrap();
+
+    // the migration is known to be applied, so it isn't run again
+    cargo_sqlx(&["migrate", "run"]).success();
+}
@@ -9,7 +9,6 @@ mod arguments;
 pub(crate) mod column;
 mod connection;
 mod database;
-mod error;
 mod kind;
 mod options;
 mod query_result;
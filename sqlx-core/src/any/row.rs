@@ -1,4 +1,3 @@
-use crate::any::error::mismatched_types;
 use crate::any::{Any, AnyColumn, AnyTypeInfo, AnyTypeInfoKind, AnyValue, AnyValueKind};
 use crate::column::{Column, ColumnIndex};
 use crate::database::Database;
@@ -6,7 +5,6 @@ use crate::decode::Decode;
 use crate::error::Error;
 use crate::ext::ustr::UStr;
 use crate::row::Row;
-use crate::type_info::TypeInfo;
 use crate::types::Type;
 use crate::value::{Value, ValueRef};
 use std::sync::Arc;
@@ -49,14 +47,8 @@ impl Row for AnyRow {
         T: Decode<'r, Self::Database> + Type<Self::Database>,
     {
         let value = self.try_get_raw(&index)?;
-        let ty = value.type_info();
 
-        if !value.is_null() && !ty.is_null() && !T::compatible(&ty) {
-            Err(mismatched_types::<T>(&ty))
-        } else {
-            T::decode(value)
-        }
-        .map_err(|source| Error::ColumnDecode {
+        T::decode_checked(value).map_err(|source| Error::ColumnDecode {
             index: format!("{index:?}"),
             source,
         })
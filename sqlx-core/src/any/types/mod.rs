@@ -10,7 +10,7 @@
 //! | `i64`                                 | BIGINT                                               |
 //! | `f32`                                 | FLOAT                                                |
 //! | `f64`                                 | DOUBLE                                               |
-//! | `&str`, [`String`]                    | VARCHAR, CHAR, TEXT                                  |
+//! | `&str`, [`String`], `Cow<'_, str>`    | VARCHAR, CHAR, TEXT                                  |
 //!
 //! # Nullable
 //!
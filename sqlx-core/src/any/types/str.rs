@@ -46,6 +46,32 @@ impl<'a> Decode<'a, Any> for &'a str {
     }
 }
 
+impl Type<Any> for Cow<'_, str> {
+    fn type_info() -> AnyTypeInfo {
+        <str as Type<Any>>::type_info()
+    }
+}
+
+impl<'q> Encode<'q, Any> for Cow<'q, str> {
+    fn encode_by_ref(
+        &self,
+        buf: &mut <Any as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.0.push(AnyValueKind::Text(self.clone()));
+        Ok(IsNull::No)
+    }
+}
+
+// Borrows from the row if the driver's value did, unlike `&str` which panics otherwise.
+impl<'r> Decode<'r, Any> for Cow<'r, str> {
+    fn decode(value: <Any as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.kind {
+            AnyValueKind::Text(text) => Ok(text),
+            other => other.unexpected(),
+        }
+    }
+}
+
 impl Type<Any> for String {
     fn type_info() -> AnyTypeInfo {
         <str as Type<Any>>::type_info()
@@ -70,3 +96,24 @@ impl<'r> Decode<'r, Any> for String {
         }
     }
 }
+
+#[test]
+fn test_decode_cow_str() {
+    use crate::any::AnyValueRef;
+
+    let borrowed = AnyValueRef {
+        kind: AnyValueKind::Text(Cow::Borrowed("foo")),
+    };
+    assert!(matches!(
+        <Cow<'_, str> as Decode<Any>>::decode(borrowed).unwrap(),
+        Cow::Borrowed("foo")
+    ));
+
+    let owned = AnyValueRef {
+        kind: AnyValueKind::Text(Cow::Owned("bar".to_string())),
+    };
+    assert!(matches!(
+        <Cow<'_, str> as Decode<Any>>::decode(owned).unwrap(),
+        Cow::Owned(text) if text == "bar"
+    ));
+}
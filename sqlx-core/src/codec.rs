@@ -0,0 +1,32 @@
+//! Provides [`Codec`] for types that can be both encoded and decoded.
+
+use crate::database::Database;
+use crate::decode::Decode;
+use crate::encode::Encode;
+
+/// A type that can be both encoded to and decoded from the database.
+///
+/// This is a shorthand for `for<'r> Decode<'r, DB> + for<'q> Encode<'q, DB>`, and is implemented
+/// for every type that implements both.
+///
+/// ```rust
+/// # use sqlx_core::codec::Codec;
+/// # use sqlx_core::database::Database;
+/// # use sqlx_core::types::Type;
+/// /// Round-trips a value through the database.
+/// fn round_trip<DB, T>(value: T)
+/// where
+///     DB: Database,
+///     T: Codec<DB> + Type<DB>,
+/// {
+///     // ...
+/// }
+/// ```
+pub trait Codec<DB: Database>: for<'r> Decode<'r, DB> + for<'q> Encode<'q, DB> {}
+
+impl<DB, T> Codec<DB> for T
+where
+    DB: Database,
+    T: for<'r> Decode<'r, DB> + for<'q> Encode<'q, DB>,
+{
+}
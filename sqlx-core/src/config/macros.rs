@@ -0,0 +1,702 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Configuration for the `query!()` family of macros.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Specify the environment variable to read the database URL from, or a list of variables
+    /// to try in order.
+    ///
+    /// This is used when the macros are invoked online, i.e. when `SQLX_OFFLINE` is not set
+    /// and the query is described against a live database. With a list, the first variable
+    /// that is set is used.
+    ///
+    /// Defaults to `DATABASE_URL` if not set or empty. Like `DATABASE_URL`, the variables may
+    /// also be set in a `.env` file.
+    ///
+    /// Useful when a project talks to multiple databases, so that each crate can be checked
+    /// against the right one.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// database_url_var = "FOO_DATABASE_URL"
+    /// ```
+    ///
+    /// `.env`:
+    /// ```text
+    /// FOO_DATABASE_URL=postgres://postgres@localhost:5432/foo
+    /// ```
+    ///
+    /// To prefer a test database where one is configured:
+    /// ```toml
+    /// [macros]
+    /// database_url_var = ["DATABASE_URL_TEST", "DATABASE_URL"]
+    /// ```
+    #[serde(deserialize_with = "one_or_many")]
+    pub database_url_var: Option<Vec<String>>,
+
+    /// The environment variable to read the database URL from for queries with a
+    /// `#[driver = "..."]` hint, keyed by a URL scheme of the driver.
+    ///
+    /// This lets one crate check queries against more than one database at compile time.
+    /// A query with a hint reads the URL from the variable for its driver only, and is
+    /// only expanded with that driver; queries without a hint keep using
+    /// [`database_url_var`][Self::database_url_var].
+    ///
+    /// Using a hint for a driver that isn't listed here is a compile error.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros.driver_url_vars]
+    /// postgres = "PG_DATABASE_URL"
+    /// sqlite = "SQLITE_DATABASE_URL"
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// let user = sqlx::query!(#[driver = "sqlite"] "SELECT name FROM users WHERE id = ?", id)
+    ///     .fetch_one(&sqlite_pool)
+    ///     .await?;
+    /// ```
+    pub driver_url_vars: HashMap<String, String>,
+
+    /// Spawn a command that forwards a local port to the database before describing queries.
+    ///
+    /// For databases that are only reachable through a bastion host or another kind of
+    /// tunnel. The command is spawned the first time a query is described against a live
+    /// database, and SQLx waits until `local_port` on `127.0.0.1` accepts connections.
+    /// The host and port of the database URL are then replaced with `127.0.0.1:<local_port>`;
+    /// everything else in the URL (credentials, database name, parameters) is kept as-is.
+    ///
+    /// This only affects compile-time connections made by the macros. It has no effect at
+    /// runtime, or when the macros are reading from the offline query cache.
+    ///
+    /// #### Lifecycle
+    /// The command is spawned at most once per compiler process and reused for every query
+    /// in the crate. Its stdin is a pipe held open by the compiler, and describe connections
+    /// stay open until the compiler exits, so the tunnel is torn down when the compiler exits
+    /// if the command stops on EOF or once its forwarded connections are closed.
+    ///
+    /// If the command exits, or the port does not come up within `ready_timeout_secs`,
+    /// every query that would be described live fails with an error saying so; the command
+    /// is not restarted within the same compiler process.
+    ///
+    /// #### Example
+    /// Using `ssh -L` to reach a Postgres server that is only visible from `bastion.internal`:
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros.describe_proxy_command]
+    /// # `sleep` keeps the session alive long enough for the first connection;
+    /// # after that, `ssh` waits for forwarded connections to close before exiting.
+    /// command = [
+    ///     "ssh", "-o", "ExitOnForwardFailure=yes",
+    ///     "-L", "15432:db.internal:5432", "bastion.internal", "sleep", "30",
+    /// ]
+    /// local_port = 15432
+    /// ```
+    ///
+    /// `ssh` must be able to authenticate non-interactively (e.g. through `ssh-agent`),
+    /// as there is no terminal to prompt for a password.
+    pub describe_proxy_command: Option<DescribeProxyCommand>,
+
+    /// The `search_path` to set on connections made to describe queries (Postgres only).
+    ///
+    /// Unqualified table and type names in queries are resolved against the `search_path`
+    /// of the describe connection, which otherwise comes from the server or role defaults.
+    /// Setting this makes the macros resolve them the same way the application does if it
+    /// sets its own `search_path` at runtime.
+    ///
+    /// The value is passed as-is, so multiple schemas are separated by commas.
+    ///
+    /// This only affects compile-time connections made by the macros. Describing a query
+    /// against a database other than Postgres is an error while this is set.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// search_path = "app, public"
+    /// ```
+    pub search_path: Option<String>,
+
+    /// Allow `#[timeout = "..."]` hints on `query!()`, `query_as!()` and `query_scalar!()`.
+    ///
+    /// A hint sets [`Query::timeout`] on the generated query, so executing it fails with
+    /// [`Error::QueryTimedOut`] if it does not complete in time. The duration is an integer
+    /// followed by a unit: `ms`, `s` or `m`, e.g. `"200ms"`.
+    ///
+    /// In Postgres the timeout is applied on the server as `statement_timeout`, so the statement
+    /// is cancelled. Other drivers enforce it on the client and the database keeps executing the
+    /// statement; pair it with a server-side limit (e.g. `max_execution_time` in MySQL)
+    /// if the statement itself should be aborted.
+    ///
+    /// Using a hint while this is `false` (the default) is a compile error.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// enable_timeout_hints = true
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// let user = sqlx::query!(#[timeout = "200ms"] "SELECT name FROM users WHERE id = $1", id)
+    ///     .fetch_one(&pool)
+    ///     .await?;
+    /// ```
+    ///
+    /// [`Query::timeout`]: crate::query::Query::timeout
+    /// [`Error::QueryTimedOut`]: crate::error::Error::QueryTimedOut
+    pub enable_timeout_hints: bool,
+
+    /// Column names whose values are hidden in the `Debug` output of generated records.
+    ///
+    /// The anonymous `Record` struct generated by `query!()` normally derives `Debug`, which
+    /// makes it easy to leak password hashes or tokens into logs. If any column of the query
+    /// is listed here, `Record` gets a manual `Debug` impl instead, which prints `"***"`
+    /// in place of those fields. The values are still decoded and accessible as usual.
+    ///
+    /// Names are matched exactly against the column name in the query, without any
+    /// [type or nullability override][overrides] and before [`rename_all`][Self::rename_all]
+    /// is applied. The other column lists in this section are matched the same way.
+    /// Structs passed to `query_as!()` are not affected, as they bring their own `Debug` impl.
+    ///
+    /// [overrides]: https://docs.rs/sqlx/latest/sqlx/macro.query.html#type-overrides-output-columns
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// redacted_columns = ["password_hash", "api_token"]
+    /// ```
+    pub redacted_columns: Vec<String>,
+
+    /// Mark the `Record` struct generated by `query!()` as `#[non_exhaustive]`.
+    ///
+    /// The macro's own construction of the struct is unaffected, as it happens in the
+    /// same crate. This only matters if the record type is made visible to other crates,
+    /// where it prevents them from constructing it or destructuring it exhaustively,
+    /// so that adding a column to the query is not a breaking change for them.
+    ///
+    /// Defaults to `false`.
+    pub record_non_exhaustive: bool,
+
+    /// Additional traits to derive for the `Record` struct generated by `query!()`.
+    ///
+    /// Each entry is a path to a derive macro, which must resolve where the macro is invoked,
+    /// and is added to `#[derive(Debug)]` as-is. `Record` always implements `Debug`, so it
+    /// should not be listed here, and neither should `PartialEq`, `Eq` or `Hash` if
+    /// [`record_key_columns`][Self::record_key_columns] is set.
+    ///
+    /// As with any derive, the type of every column must implement the traits as well.
+    /// Structs passed to `query_as!()` are not affected.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// record_derives = ["Clone", "serde::Serialize"]
+    /// ```
+    pub record_derives: Vec<String>,
+
+    /// Column names that identify a row, for `PartialEq`, `Eq` and `Hash` impls on generated
+    /// records.
+    ///
+    /// If any column of a `query!()` is listed here, its `Record` struct implements these
+    /// traits by comparing and hashing only those fields. All other fields are ignored for
+    /// equality, so two records with the same key are equal even if the rest of the row
+    /// differs. This allows deduplicating rows in a `HashSet` by their primary key, even if
+    /// other columns hold types like `f64` that can't implement `Eq` or `Hash`.
+    ///
+    /// The key fields themselves must implement `Eq` and `Hash`. Names are matched the same
+    /// way as [`redacted_columns`][Self::redacted_columns]. Structs passed to `query_as!()`
+    /// are not affected.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// record_key_columns = ["id"]
+    /// ```
+    pub record_key_columns: Vec<String>,
+
+    /// Use these Rust types for output columns of the given SQL types, instead of the
+    /// default mapping.
+    ///
+    /// Keys are type names as reported by the database, compared case-insensitively;
+    /// values are Rust type paths, which must resolve where the macro is invoked and
+    /// implement `Decode` for the database. This saves writing the same `as "col: Type"`
+    /// override on every query returning a custom type, like an enum or an extension type
+    /// such as Postgres' `citext`. An override written in the query still takes precedence.
+    ///
+    /// As with `as "col: Type"`, the type is trusted without checking it against the column.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros.type_overrides]
+    /// citext = "String"
+    /// status = "crate::models::Status"
+    /// ```
+    pub type_overrides: HashMap<String, String>,
+
+    /// Column names to always treat as nullable, generating `Option<T>` fields for them.
+    ///
+    /// For columns that the database infers to be `NOT NULL` but can still be `NULL`, e.g.
+    /// some columns from the nullable side of an outer join. Names are matched the same way as
+    /// [`redacted_columns`][Self::redacted_columns]. A `!` override on the column in the query
+    /// still takes precedence.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// force_nullable = ["manager_name"]
+    /// ```
+    pub force_nullable: Vec<String>,
+
+    /// Like [`force_nullable`][Self::force_nullable], but only for the query with the
+    /// given hash.
+    ///
+    /// The hash is the hex-encoded SHA-256 of the query text with comments (including `#`
+    /// comments, for every database) stripped and whitespace collapsed, as used in the file
+    /// names of `.sqlx/query-<hash>.json`. Editing the query other than reformatting it changes
+    /// its hash, so the entry must be updated along with it.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros.query_force_nullable]
+    /// "8c2f6d3e..." = ["manager_name"]
+    /// ```
+    pub query_force_nullable: HashMap<String, Vec<String>>,
+
+    /// Convert column names to this case for the field names of records generated by
+    /// `query!()`, and for the fields that `query_as!()` assigns.
+    ///
+    /// The conversion applies to the column name before any `!`, `?` or `: Type` override,
+    /// so `"userId!"` becomes `user_id` with `rename_all = "snake_case"`. A column whose
+    /// converted name isn't a valid Rust identifier is a compile error.
+    /// The column lists in this section, like [`force_nullable`][Self::force_nullable], and
+    /// `/* sqlx:null=... */` hints still match the original column names.
+    ///
+    /// Defaults to `"verbatim"`, using column names as they are.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// rename_all = "camelCase"
+    /// ```
+    pub rename_all: RenameAll,
+
+    /// Allow passing the bind parameters of `query!()` and its variants as the fields of a struct.
+    ///
+    /// Instead of positional arguments, the macro accepts a single `params = <expr>` argument,
+    /// where `<expr>` is a value (or reference) of any struct with a field for each `$name`
+    /// placeholder in the query; see [`enable_named_params`][Self::enable_named_params], which
+    /// must be enabled as well. The struct is declared by the caller, so it can be built ahead
+    /// of time, passed around and reused. Fields are type-checked like other arguments, so an
+    /// `Option<T>` field binds a nullable parameter.
+    ///
+    /// Using `params = ..` while this is `false` (the default) is a compile error.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// enable_named_params = true
+    /// enable_params_struct = true
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// struct AccountKey {
+    ///     id: i32,
+    ///     name: Option<String>,
+    /// }
+    ///
+    /// let key = AccountKey { id: 1, name: Some("Herp Derpinson".to_string()) };
+    ///
+    /// let account = sqlx::query!(
+    ///     "SELECT * FROM accounts WHERE id = $id AND name = $name",
+    ///     params = &key,
+    /// )
+    /// .fetch_one(&mut conn)
+    /// .await?;
+    /// ```
+    pub enable_params_struct: bool,
+
+    /// Allow `$name` bind parameters in `query!()` and its variants, for Postgres and SQLite.
+    ///
+    /// Each `$name` is bound to the argument that is the variable `name` (possibly cast, as in
+    /// `name as _`), so a value used more than once in the query is only passed once. Before
+    /// the query is described, the placeholders are numbered `$1`, `$2`, ... in order of their
+    /// first use and the arguments reordered to match; the database never sees the names.
+    ///
+    /// A query may not mix `$name` and numbered placeholders. This is off by default since
+    /// MySQL allows `$` at the start of identifiers, and it has no numbered placeholders to
+    /// rewrite to.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// enable_named_params = true
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// let accounts = sqlx::query!(
+    ///     "SELECT * FROM accounts WHERE owner = $user_id OR created_by = $user_id LIMIT $limit",
+    ///     limit,
+    ///     user_id,
+    /// )
+    /// .fetch_all(&pool)
+    /// .await?;
+    /// ```
+    pub enable_named_params: bool,
+
+    /// If describing a query fails, fall back to what the database reports from just
+    /// preparing it.
+    ///
+    /// Describing a query involves more than preparing it: Postgres, for example, also runs
+    /// `EXPLAIN` on the prepared statement (with every parameter set to `NULL`) to infer the
+    /// nullability of output columns. That extra step fails for some statements even though
+    /// they prepare fine, most commonly when a parameter is of a domain type declared
+    /// `NOT NULL`.
+    ///
+    /// With this enabled, such queries are still checked for syntax and the number (and, for
+    /// Postgres, the types) of their parameters, but the nullability of every output column is
+    /// unknown, so all of them are treated as nullable. If preparing the query fails too,
+    /// the original error is reported.
+    ///
+    /// Defaults to `false`.
+    pub describe_fallback: bool,
+
+    /// The Rust type to use for output columns whose SQL type can't be mapped, when the query
+    /// was described by [`describe_fallback`][Self::describe_fallback].
+    ///
+    /// The type must implement `Decode` for the database, e.g. `"String"`.
+    /// If not set, such columns are a compile error, as they are normally.
+    pub describe_fallback_type: Option<String>,
+
+    /// Write a digest of every query in the crate to this path, relative to the
+    /// directory containing `Cargo.toml`.
+    ///
+    /// The digest is the SHA-256 of the hashes of all distinct queries expanded by the
+    /// `query!()` family of macros, sorted so that it doesn't depend on the order in which
+    /// the compiler expands them. Two builds compiling the same set of queries produce the same
+    /// digest, so CI can check it against a known value to detect unexpected changes to the SQL
+    /// a crate contains.
+    ///
+    /// The file is rewritten as queries are expanded and is complete once the crate has
+    /// finished compiling. It is not updated if the crate is not recompiled. Use a different
+    /// path for each crate in a workspace.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// query_digest_path = "target/sqlx-query-digest.txt"
+    /// ```
+    pub query_digest_path: Option<PathBuf>,
+
+    /// Load cached query data from this directory, relative to the directory containing
+    /// `Cargo.toml`, when building offline or without a database URL.
+    ///
+    /// The directory is checked after `SQLX_OFFLINE_DIR`, which still takes precedence if set,
+    /// and before the `.sqlx` directories of the crate and the workspace. This lets a checked-in
+    /// `sqlx.toml` point the build at the query data without setting environment variables,
+    /// e.g. in CI.
+    ///
+    /// Query data is still only saved to `SQLX_OFFLINE_DIR`, so populate this directory by
+    /// moving the output of `cargo sqlx prepare` into it, or by building online with
+    /// `SQLX_OFFLINE_DIR` set to it.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// offline_dir = "sqlx-data"
+    /// ```
+    pub offline_dir: Option<PathBuf>,
+
+    /// Build offline, from the cached query data only, as if `SQLX_OFFLINE=true` were set.
+    ///
+    /// `SQLX_OFFLINE` still takes precedence if set, so `SQLX_OFFLINE=false` builds online
+    /// regardless of this setting; `cargo sqlx prepare` relies on that.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// offline = true
+    /// ```
+    pub offline: Option<bool>,
+
+    /// Build online, but expand queries from the cached query data if the database can't be
+    /// reached, as if `SQLX_OFFLINE_FALLBACK=true` were set.
+    ///
+    /// Only errors connecting to the database cause a fallback; if the database is reachable
+    /// but describing the query fails, that error is reported as usual. Queries without cached
+    /// data still fail to compile if the database is down. A warning saying the cached data was
+    /// used is printed for each query that falls back.
+    ///
+    /// `SQLX_OFFLINE_FALLBACK` takes precedence if set. `cargo sqlx prepare` always disables
+    /// the fallback, as it needs every query to be described by the database.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// offline_fallback = true
+    /// ```
+    pub offline_fallback: Option<bool>,
+
+    /// Save cached query data gzipped, as `query-<hash>.json.gz` instead of `query-<hash>.json`.
+    ///
+    /// Saving either kind of file deletes the other one for the same query, so switching this
+    /// setting and re-running `cargo sqlx prepare` converts the whole directory.
+    /// Both kinds of file are always loaded regardless of this setting; if both exist for the
+    /// same query, the uncompressed file is used.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// offline_compression = true
+    /// ```
+    pub offline_compression: bool,
+
+    /// Save cached query data to a single `queries.json` file, keyed by query hash, instead of
+    /// a `query-<hash>.json` file per query.
+    ///
+    /// This keeps the directory to one file, with its entries sorted by hash so that the file
+    /// is the same no matter in which order the queries were compiled. Concurrent builds
+    /// writing to the same directory take turns using a `queries.json.lock` file, which is
+    /// deleted again afterwards; a lock file left behind by a build that was killed must be
+    /// deleted by hand.
+    ///
+    /// `offline_compression` doesn't apply to this file. `queries.json` is always loaded if
+    /// it exists, after the `query-<hash>.json` files in the same directory.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// offline_single_file = true
+    /// ```
+    pub offline_single_file: bool,
+
+    /// Before describing queries against a live database, check that its latest applied
+    /// migration is the latest one in `migrate.migrations_dir`.
+    ///
+    /// This turns the type errors or unknown-table errors from building against a database
+    /// that hasn't been migrated into a message saying so. The check runs once per crate
+    /// and is skipped when building offline.
+    ///
+    /// Requires the `migrate` feature of SQLx.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// check_migrations = true
+    /// ```
+    pub check_migrations: bool,
+
+    /// Check the number of arguments to `query_unchecked!()` and its variants against the bind
+    /// parameters in the SQL, with a `const` assertion in the generated code.
+    ///
+    /// The parameters are counted by scanning the query text, skipping string literals, quoted
+    /// identifiers and comments, so this doesn't rely on the parameter information from
+    /// describing the query; it still applies if that information is unavailable. Queries using
+    /// named parameters (SQLite only) are not checked.
+    ///
+    /// Without this, the unchecked macros don't check the number of arguments at all.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// assert_param_count = true
+    /// ```
+    pub assert_param_count: bool,
+
+    /// Expand queries from hand-written describe data in this file, relative to the directory
+    /// containing `Cargo.toml`, instead of a live database or cached data.
+    ///
+    /// **For development only.** Nothing checks the mock against a real database: a query is
+    /// expanded with whatever column names, types and nullability the file declares, and a
+    /// mistake only shows up at runtime as a decode error. Use it to work on queries for tables
+    /// that don't exist yet, or without access to a database, and remove it before shipping.
+    ///
+    /// Queries that don't match any entry in the file are expanded as usual. Mocked queries
+    /// are not saved to the query cache.
+    ///
+    /// The file is JSON, listing the database the queries are for (as named by
+    /// `Database::NAME`) and an entry for each query. In the `query` pattern, `*` matches any
+    /// text and runs of whitespace compare equal; the first matching entry is used.
+    /// `params` are the Rust types of the bind parameters, and `nullable` defaults to `false`:
+    ///
+    /// ```json
+    /// {
+    ///     "database": "PostgreSQL",
+    ///     "queries": [
+    ///         {
+    ///             "query": "SELECT id, name FROM users WHERE id = $1",
+    ///             "params": ["i64"],
+    ///             "columns": [
+    ///                 { "name": "id", "type": "i64" },
+    ///                 { "name": "name", "type": "String", "nullable": true }
+    ///             ]
+    ///         }
+    ///     ]
+    /// }
+    /// ```
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros]
+    /// mock_describe = "sqlx-mock-describe.json"
+    /// ```
+    pub mock_describe: Option<PathBuf>,
+
+    /// Settings for individual queries, keyed by the name given to the query with a
+    /// `#[name = "..."]` hint.
+    ///
+    /// This keeps column overrides for a query in one place instead of in its SQL.
+    /// Naming a query that has no entry here is a compile error, as is an entry referring
+    /// to a column the query doesn't return.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [macros.queries.user_by_id]
+    /// nullable = ["email"]
+    /// non_null = ["name"]
+    /// column_types = { status = "crate::models::Status" }
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// let user = sqlx::query!(#[name = "user_by_id"] "SELECT name, email, status FROM users WHERE id = $1", id)
+    ///     .fetch_one(&pool)
+    ///     .await?;
+    /// ```
+    pub queries: HashMap<String, QueryConfig>,
+}
+
+/// The settings for a named query, in [`Config::queries`].
+///
+/// Columns are named the same way as in [`Config::redacted_columns`]. An override written
+/// in the query itself, like `as "email!"`, still takes precedence.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QueryConfig {
+    /// Columns to treat as nullable, like `as "col?"`.
+    pub nullable: Vec<String>,
+
+    /// Columns to treat as `NOT NULL`, like `as "col!"`.
+    pub non_null: Vec<String>,
+
+    /// Rust types for columns, like `as "col: Type"`. `"_"` leaves the type to inference.
+    pub column_types: HashMap<String, String>,
+}
+
+/// The case to convert column names to, for [`Config::rename_all`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, serde::Deserialize)]
+pub enum RenameAll {
+    /// Use column names as they are.
+    #[default]
+    #[serde(rename = "verbatim")]
+    Verbatim,
+    /// `user_id`
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+    /// `userId`
+    #[serde(rename = "camelCase")]
+    CamelCase,
+    /// `UserId`
+    #[serde(rename = "PascalCase")]
+    PascalCase,
+}
+
+/// The command to spawn for [`Config::describe_proxy_command`].
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DescribeProxyCommand {
+    /// The program to run, followed by its arguments.
+    ///
+    /// The program is looked up in `PATH`; no shell is involved.
+    pub command: Vec<String>,
+
+    /// The local port that the command forwards to the database.
+    pub local_port: u16,
+
+    /// How long to wait for `local_port` to accept connections, in seconds.
+    ///
+    /// Defaults to 10 seconds.
+    pub ready_timeout_secs: Option<u64>,
+}
+
+/// Accept either a single string or a list of strings.
+fn one_or_many<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match serde::Deserialize::deserialize(deserializer)? {
+        OneOrMany::One(var) => Some(vec![var]),
+        OneOrMany::Many(vars) => Some(vars),
+    })
+}
+
+#[test]
+fn test_database_url_var_one_or_many() {
+    let config: Config = toml::from_str("database_url_var = \"FOO_URL\"").unwrap();
+    assert_eq!(config.database_url_var, Some(vec!["FOO_URL".to_string()]));
+
+    let config: Config = toml::from_str("database_url_var = [\"FOO_URL\", \"BAR_URL\"]").unwrap();
+    assert_eq!(
+        config.database_url_var,
+        Some(vec!["FOO_URL".to_string(), "BAR_URL".to_string()])
+    );
+
+    let config: Config = toml::from_str("").unwrap();
+    assert_eq!(config.database_url_var, None);
+
+    assert!(toml::from_str::<Config>("database_url_var = 1").is_err());
+}
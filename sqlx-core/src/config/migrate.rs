@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+/// Configuration for migrations when executed using `sqlx::migrate!()` or through `sqlx-cli`.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The directory containing the crate's migrations, relative to the directory containing
+    /// `Cargo.toml`.
+    ///
+    /// Defaults to `migrations`.
+    ///
+    /// Used by `sqlx::migrate!()` when invoked without an argument, by `#[sqlx::test]` when
+    /// inferring the migrations to apply, by `macros.check_migrations`, and by `sqlx migrate`
+    /// without `--source`. An explicit argument takes precedence.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate]
+    /// migrations_dir = "db/migrations"
+    /// ```
+    pub migrations_dir: Option<PathBuf>,
+
+    /// The table recording the applied migrations.
+    ///
+    /// Defaults to `_sqlx_migrations`.
+    ///
+    /// Sets the table name of the `Migrator` expanded by `sqlx::migrate!()` and the one used by
+    /// `sqlx migrate`; see `Migrator::set_table_name()` for the runtime equivalent. The name is
+    /// inserted into the SQL as-is, so it may be schema-qualified.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate]
+    /// table_name = "app._migrations"
+    /// ```
+    pub table_name: Option<String>,
+}
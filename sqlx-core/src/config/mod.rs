@@ -0,0 +1,698 @@
+//! Configuration for SQLx, read from a `sqlx.toml` file in the root of the crate.
+//!
+//! Currently, this is only read by the proc macros at compile-time; `sqlx.toml` has no effect
+//! on the behavior of SQLx at runtime, except for the `[pool]` section if the application reads
+//! the file itself (e.g. with [`Config::read_from_path()`]) and passes it to
+//! `PoolOptions::from_config()`.
+//!
+//! The file is looked up relative to `CARGO_MANIFEST_DIR`, so in a workspace each crate
+//! that invokes the macros reads the `sqlx.toml` next to its own `Cargo.toml`.
+//!
+//! Every key is optional; the sections and keys are described on [`Config`] and its fields.
+//! Unknown keys are an error, so that a typo doesn't silently leave a setting at its default.
+//! The error suggests the closest valid key, if there is one.
+//!
+//! ```toml
+//! [macros]
+//! # Read the database URL from a different environment variable than `DATABASE_URL`.
+//! database_url_var = "FOO_DATABASE_URL"
+//! ```
+//!
+//! String values may reference environment variables as `${VAR}`, which is replaced with the
+//! value of `VAR` when the file is read; it is an error if `VAR` is not set. Use `$$` for a
+//! literal `$`. Any other `$` is kept as-is.
+//!
+//! ```toml
+//! [macros]
+//! offline_dir = "${SQLX_CACHE_ROOT}/my-crate"
+//! ```
+use std::cmp;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{PoisonError, RwLock};
+
+use once_cell::sync::Lazy;
+
+/// Configuration for the `query!()` family of macros.
+#[cfg(feature = "config-macros")]
+pub mod macros;
+
+/// Configuration for migrations when executed using `sqlx::migrate!()` or through `sqlx-cli`.
+#[cfg(feature = "config-migrate")]
+pub mod migrate;
+
+/// Configuration for connection pools, applied with `PoolOptions::from_config()`.
+#[cfg(feature = "config-pool")]
+pub mod pool;
+
+/// The parsed structure of a `sqlx.toml` file.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Configuration for the `query!()` family of macros.
+    ///
+    /// See type documentation for details.
+    #[cfg(feature = "config-macros")]
+    pub macros: macros::Config,
+
+    /// Configuration for migrations when executed using `sqlx::migrate!()` or through `sqlx-cli`.
+    ///
+    /// See type documentation for details.
+    #[cfg(feature = "config-migrate")]
+    pub migrate: migrate::Config,
+
+    /// Configuration for connection pools, applied with `PoolOptions::from_config()`.
+    ///
+    /// See type documentation for details.
+    #[cfg(feature = "config-pool")]
+    pub pool: pool::Config,
+
+    // Sections for disabled features are still valid keys, but are not parsed.
+    #[cfg(not(feature = "config-macros"))]
+    #[serde(rename = "macros")]
+    _macros: serde::de::IgnoredAny,
+
+    #[cfg(not(feature = "config-migrate"))]
+    #[serde(rename = "migrate")]
+    _migrate: serde::de::IgnoredAny,
+
+    #[cfg(not(feature = "config-pool"))]
+    #[serde(rename = "pool")]
+    _pool: serde::de::IgnoredAny,
+}
+
+/// Error returned from various methods of [`Config`].
+#[derive(thiserror::Error, Debug)]
+pub enum ConfigError {
+    /// The loading method expected `CARGO_MANIFEST_DIR` to be set and it wasn't.
+    ///
+    /// This is necessary to locate the root of the crate currently being compiled.
+    ///
+    /// See [the "Environment Variables" page of the Cargo Book][cargo-env] for details.
+    ///
+    /// [cargo-env]: https://doc.rust-lang.org/cargo/reference/environment-variables.html#environment-variables-cargo-sets-for-crates
+    #[error("environment variable `CARGO_MANIFEST_DIR` must be set and valid")]
+    Env(
+        #[from]
+        #[source]
+        std::env::VarError,
+    ),
+
+    /// No config file exists at `path`.
+    ///
+    /// Callers that treat the file as optional can check for this with
+    /// [`ConfigError::is_missing()`].
+    #[error("config file {path:?} not found")]
+    Missing { path: PathBuf },
+
+    /// An I/O error occurred while attempting to read the config file at `path`.
+    ///
+    /// A file that doesn't exist is reported as [`ConfigError::Missing`] instead.
+    #[error("error reading config file {path:?}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        error: io::Error,
+    },
+
+    /// An error in the TOML was encountered while parsing the config file at `path`.
+    ///
+    /// The error gives the line (for syntax errors) or the key (for invalid values) and context
+    /// when printed with `Display`/`ToString`.
+    #[error("error parsing config file {path:?}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        error: toml::de::Error,
+    },
+
+    /// The config file at `path` references the environment variable `var`
+    /// as `${var}`, and it is not set or not valid Unicode.
+    #[error("environment variable `{var}` referenced by config file {path:?} is not set")]
+    Interpolate { path: PathBuf, var: String },
+}
+
+impl ConfigError {
+    /// `true` if the config file doesn't exist.
+    pub fn is_missing(&self) -> bool {
+        matches!(self, Self::Missing { .. })
+    }
+}
+
+/// Configs that were read successfully, keyed by the path they were read from.
+///
+/// A proc-macro process may expand macros for more than one crate, e.g. with rust-analyzer,
+/// so each crate's `sqlx.toml` gets its own entry. Entries are leaked to hand out `&'static`s,
+/// which is fine since there are only ever a handful of them, plus one per [`Config::reload()`].
+static CACHE: Lazy<RwLock<HashMap<PathBuf, &'static Config>>> = Lazy::new(Default::default);
+
+/// Returned from [`Config::try_get()`] if there is no `sqlx.toml`.
+static DEFAULT: Lazy<Config> = Lazy::new(Config::default);
+
+impl Config {
+    /// Get the cached config, or attempt to read `$CARGO_MANIFEST_DIR/sqlx.toml`, using
+    /// [`Config::default()`] if the file doesn't exist.
+    ///
+    /// A config that was read is cached per path and returned by future calls for the same
+    /// `CARGO_MANIFEST_DIR`.
+    ///
+    /// ### Panics
+    /// If `CARGO_MANIFEST_DIR` is not set, or if the file exists but could not be read or parsed.
+    /// Use [`Self::try_get()`] to handle the error instead.
+    pub fn get() -> &'static Self {
+        Self::try_get().unwrap()
+    }
+
+    /// Get the cached config, or attempt to read `$CARGO_MANIFEST_DIR/sqlx.toml`, using
+    /// [`Config::default()`] if the file doesn't exist.
+    ///
+    /// The default is the same as the config parsed from an empty file. A config that was read
+    /// is cached per path and returned by future calls for the same `CARGO_MANIFEST_DIR`;
+    /// a missing file is not, so a `sqlx.toml` created later is still picked up.
+    ///
+    /// Errors if `CARGO_MANIFEST_DIR` is not set, or if the config file exists but could not be
+    /// read or parsed.
+    pub fn try_get() -> Result<&'static Self, ConfigError> {
+        match Self::try_get_with(Self::default_path) {
+            Err(e) if e.is_missing() => Ok(&DEFAULT),
+            res => res,
+        }
+    }
+
+    /// Equivalent to [`Self::try_get()`].
+    #[deprecated(note = "use `Config::try_get()`, which also defaults if the file is missing")]
+    pub fn try_get_or_default() -> Result<&'static Self, ConfigError> {
+        Self::try_get()
+    }
+
+    /// Get the cached config for the path returned by the closure, or attempt to read it.
+    ///
+    /// On success, the config is cached per path and returned by future calls for the same path.
+    ///
+    /// Errors if the config file does not exist, or could not be read or parsed. Unlike
+    /// [`Self::try_get()`], a missing file is an error; check for it with
+    /// [`ConfigError::is_missing()`].
+    pub fn try_get_with(
+        make_path: impl FnOnce() -> Result<PathBuf, ConfigError>,
+    ) -> Result<&'static Self, ConfigError> {
+        Self::cached(make_path()?, Self::read_from)
+    }
+
+    /// Get the cached config for `path`, or attempt to read it.
+    ///
+    /// On success, the config is cached per path and returned by future calls for the same path,
+    /// the same as [`Self::try_get_with()`].
+    ///
+    /// Errors if the config file does not exist, or could not be read or parsed.
+    pub fn try_get_with_path(path: &Path) -> Result<&'static Self, ConfigError> {
+        Self::cached(path.to_path_buf(), Self::read_from)
+    }
+
+    /// Read the config at `path`, bypassing the cache.
+    ///
+    /// For tools that load a specific `sqlx.toml` and may read it again after it changes;
+    /// unlike the cached methods, the config isn't leaked. Errors if the file does not exist,
+    /// or could not be read or parsed.
+    pub fn read_from_path(path: impl Into<PathBuf>) -> Result<Self, ConfigError> {
+        Self::read_from(path.into())
+    }
+
+    /// Re-read `$CARGO_MANIFEST_DIR/sqlx.toml`, replacing the cached config.
+    ///
+    /// Future calls of the other methods for the same `CARGO_MANIFEST_DIR` return the new config.
+    /// References returned before remain valid but keep pointing to the old config.
+    ///
+    /// Errors if `CARGO_MANIFEST_DIR` is not set, or if the config file does not exist or could
+    /// not be read or parsed. The cached config is cleared either way.
+    pub fn reload() -> Result<&'static Self, ConfigError> {
+        Self::reload_with_path(&Self::default_path()?)
+    }
+
+    /// Re-read the config at `path`, replacing the cached config for it.
+    ///
+    /// See [`Self::reload()`] for details.
+    pub fn reload_with_path(path: &Path) -> Result<&'static Self, ConfigError> {
+        let mut cache = CACHE.write().unwrap_or_else(PoisonError::into_inner);
+
+        cache.remove(path);
+
+        Self::insert(&mut cache, path.to_path_buf(), Self::read_from)
+    }
+
+    /// Look up `path` in [`CACHE`], or `read` it and cache the result if that succeeds.
+    fn cached(
+        path: PathBuf,
+        read: impl FnOnce(PathBuf) -> Result<Self, ConfigError>,
+    ) -> Result<&'static Self, ConfigError> {
+        if let Some(config) = CACHE
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&path)
+        {
+            return Ok(config);
+        }
+
+        // The write lock is held while reading so the same file isn't read and leaked twice.
+        let mut cache = CACHE.write().unwrap_or_else(PoisonError::into_inner);
+
+        // Another thread may have read it while we were waiting for the lock.
+        if let Some(config) = cache.get(&path) {
+            return Ok(config);
+        }
+
+        Self::insert(&mut cache, path, read)
+    }
+
+    fn insert(
+        cache: &mut HashMap<PathBuf, &'static Self>,
+        path: PathBuf,
+        read: impl FnOnce(PathBuf) -> Result<Self, ConfigError>,
+    ) -> Result<&'static Self, ConfigError> {
+        let config: &'static Self = Box::leak(Box::new(read(path.clone())?));
+        cache.insert(path, config);
+
+        Ok(config)
+    }
+
+    fn default_path() -> Result<PathBuf, ConfigError> {
+        let mut path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR")?);
+        path.push("sqlx.toml");
+        Ok(path)
+    }
+
+    fn read_from(path: PathBuf) -> Result<Self, ConfigError> {
+        // The `toml` crate doesn't provide an incremental reader.
+        let toml_s = match std::fs::read_to_string(&path) {
+            Ok(toml) => toml,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                return Err(ConfigError::Missing { path });
+            }
+            Err(error) => {
+                return Err(ConfigError::Read { path, error });
+            }
+        };
+
+        tracing::debug!("read config TOML from {path:?}:\n{toml_s}");
+
+        let table: toml::Table = toml_s.parse().map_err(|error| ConfigError::Parse {
+            path: path.clone(),
+            error,
+        })?;
+
+        let mut value = toml::Value::Table(table);
+
+        if let Err(var) = interpolate_value(&mut value) {
+            return Err(ConfigError::Interpolate { path, var });
+        }
+
+        value.try_into().map_err(|error| ConfigError::Parse {
+            path,
+            error: with_suggestion(error),
+        })
+    }
+}
+
+/// Add the closest valid name to an "unknown field" or "unknown variant" error, e.g.
+/// "did you mean `macros`?" for `[marcos]`.
+fn with_suggestion(error: toml::de::Error) -> toml::de::Error {
+    let message = error.message();
+
+    // the message is followed by the key it's for, as "in `macros`", if any
+    let display = error.to_string();
+    let Some(context) = display.strip_prefix(message) else {
+        return error;
+    };
+
+    match suggest(message) {
+        Some(name) => serde::de::Error::custom(format!(
+            "{message}; did you mean `{name}`?{}",
+            context.trim_end()
+        )),
+        None => error,
+    }
+}
+
+/// The expected name closest to the unknown one in a `serde` error message like
+/// "unknown field `marcos`, expected one of `macros`, `migrate`", if it's close enough.
+fn suggest(message: &str) -> Option<&str> {
+    let rest = message
+        .strip_prefix("unknown field `")
+        .or_else(|| message.strip_prefix("unknown variant `"))?;
+
+    let (unknown, expected) = rest.split_once('`')?;
+
+    // allow about one edit per three characters, like rustc does for misspelled names
+    let max_distance = cmp::max(unknown.chars().count() / 3, 1);
+
+    expected
+        .split('`')
+        .skip(1)
+        .step_by(2)
+        .map(|name| (edit_distance(unknown, name), name))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, name)| name)
+}
+
+/// The Levenshtein distance between `a` and `b`, in `char`s.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+
+    // the distances from the prefix of `a` so far to each prefix of `b`
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, &b_char) in b.iter().enumerate() {
+            let substituted = diagonal + usize::from(a_char != b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = cmp::min(substituted, cmp::min(row[j], diagonal) + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Replace `${VAR}` in all strings in `value`, recursively.
+///
+/// Returns the name of the first variable that isn't set, if any.
+fn interpolate_value(value: &mut toml::Value) -> Result<(), String> {
+    match value {
+        toml::Value::String(s) => *s = interpolate(s, |var| std::env::var(var).ok())?,
+        toml::Value::Array(values) => values.iter_mut().try_for_each(interpolate_value)?,
+        toml::Value::Table(table) => table
+            .iter_mut()
+            .try_for_each(|(_, value)| interpolate_value(value))?,
+        _ => (),
+    }
+
+    Ok(())
+}
+
+/// Replace `${VAR}` in `s` with `lookup(VAR)`, and `$$` with `$`.
+fn interpolate(s: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(i) = rest.find('$') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+
+        if let Some(after) = rest.strip_prefix("$$") {
+            out.push('$');
+            rest = after;
+        } else if let Some((var, after)) = rest
+            .strip_prefix("${")
+            .and_then(|after| after.split_once('}'))
+        {
+            out.push_str(&lookup(var).ok_or_else(|| var.to_string())?);
+            rest = after;
+        } else {
+            out.push('$');
+            rest = &rest[1..];
+        }
+    }
+
+    out.push_str(rest);
+
+    Ok(out)
+}
+
+#[test]
+fn test_default_matches_empty_file() {
+    let empty: Config = toml::from_str("").unwrap();
+
+    assert_eq!(format!("{:?}", Config::default()), format!("{empty:?}"));
+}
+
+#[test]
+#[cfg(feature = "config-macros")]
+fn test_cache_per_path() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let foo = dir.path().join("foo.toml");
+    std::fs::write(&foo, "[macros]\ndatabase_url_var = \"FOO_DATABASE_URL\"\n").unwrap();
+
+    let bar = dir.path().join("bar.toml");
+    std::fs::write(&bar, "[macros]\ndatabase_url_var = \"BAR_DATABASE_URL\"\n").unwrap();
+
+    let foo_config = Config::try_get_with_path(&foo).unwrap();
+    let bar_config = Config::try_get_with_path(&bar).unwrap();
+
+    assert_eq!(
+        foo_config.macros.database_url_var.as_deref(),
+        Some(&["FOO_DATABASE_URL".to_string()][..])
+    );
+    assert_eq!(
+        bar_config.macros.database_url_var.as_deref(),
+        Some(&["BAR_DATABASE_URL".to_string()][..])
+    );
+
+    // Cached, even if the file changes.
+    std::fs::write(&foo, "").unwrap();
+    assert!(std::ptr::eq(
+        foo_config,
+        Config::try_get_with_path(&foo).unwrap()
+    ));
+}
+
+#[test]
+#[cfg(feature = "config-macros")]
+fn test_reload_with_path() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let path = dir.path().join("sqlx.toml");
+    std::fs::write(&path, "[macros]\ndatabase_url_var = \"FOO_DATABASE_URL\"\n").unwrap();
+
+    let old = Config::try_get_with_path(&path).unwrap();
+
+    std::fs::write(&path, "[macros]\ndatabase_url_var = \"BAR_DATABASE_URL\"\n").unwrap();
+
+    let new = Config::reload_with_path(&path).unwrap();
+
+    assert_eq!(
+        new.macros.database_url_var.as_deref(),
+        Some(&["BAR_DATABASE_URL".to_string()][..])
+    );
+    assert!(std::ptr::eq(new, Config::try_get_with_path(&path).unwrap()));
+
+    // The old config is still valid.
+    assert_eq!(
+        old.macros.database_url_var.as_deref(),
+        Some(&["FOO_DATABASE_URL".to_string()][..])
+    );
+
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(Config::reload_with_path(&path).unwrap_err().is_missing());
+    assert!(Config::try_get_with_path(&path).unwrap_err().is_missing());
+}
+
+#[test]
+fn test_interpolate() {
+    let lookup = |var: &str| (var == "FOO").then(|| "foo".to_string());
+
+    assert_eq!(interpolate("${FOO}", lookup).unwrap(), "foo");
+    assert_eq!(
+        interpolate("a/${FOO}/b-${FOO}", lookup).unwrap(),
+        "a/foo/b-foo"
+    );
+    assert_eq!(interpolate("$${FOO}", lookup).unwrap(), "${FOO}");
+    assert_eq!(interpolate("$$$$", lookup).unwrap(), "$$");
+    assert_eq!(interpolate("$FOO $ ${FOO", lookup).unwrap(), "$FOO $ ${FOO");
+    assert_eq!(interpolate("${BAR}", lookup).unwrap_err(), "BAR");
+}
+
+#[test]
+#[cfg(feature = "config-macros")]
+fn test_read_interpolated() {
+    let dir = tempfile::tempdir().unwrap();
+
+    std::env::set_var("SQLX_TEST_INTERPOLATED_VAR", "FOO_DATABASE_URL");
+
+    let path = dir.path().join("sqlx.toml");
+    std::fs::write(
+        &path,
+        "[macros]\ndatabase_url_var = [\"${SQLX_TEST_INTERPOLATED_VAR}\", \"$$BAR\"]\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        Config::try_get_with_path(&path)
+            .unwrap()
+            .macros
+            .database_url_var
+            .as_deref(),
+        Some(&["FOO_DATABASE_URL".to_string(), "$BAR".to_string()][..])
+    );
+
+    let path = dir.path().join("missing.toml");
+    std::fs::write(
+        &path,
+        "[macros]\ndatabase_url_var = \"${SQLX_TEST_MISSING_VAR}\"\n",
+    )
+    .unwrap();
+
+    match Config::try_get_with_path(&path) {
+        Err(ConfigError::Interpolate { var, .. }) => assert_eq!(var, "SQLX_TEST_MISSING_VAR"),
+        res => panic!("expected an interpolation error, got {res:?}"),
+    }
+}
+
+#[test]
+fn test_unknown_key() {
+    let err = toml::from_str::<Config>("[marcos]\ndatabase_url_var = \"FOO_DATABASE_URL\"\n")
+        .unwrap_err()
+        .to_string();
+
+    assert!(err.contains("unknown field `marcos`"), "{err}");
+    assert!(err.contains("`macros`"), "{err}");
+}
+
+#[test]
+#[cfg(feature = "config-macros")]
+fn test_unknown_nested_key() {
+    let err = toml::from_str::<Config>("[macros]\ndatabase_url_vars = \"FOO_DATABASE_URL\"\n")
+        .unwrap_err()
+        .to_string();
+
+    assert!(err.contains("unknown field `database_url_vars`"), "{err}");
+    assert!(err.contains("`database_url_var`"), "{err}");
+}
+
+#[test]
+fn test_unknown_key_suggestion() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let read_err = |toml: &str| {
+        let path = dir.path().join("sqlx.toml");
+        std::fs::write(&path, toml).unwrap();
+
+        match Config::read_from_path(path) {
+            Err(ConfigError::Parse { error, .. }) => error.to_string(),
+            res => panic!("expected a parse error, got {res:?}"),
+        }
+    };
+
+    let err = read_err("[marcos]\n");
+    assert!(err.contains("unknown field `marcos`"), "{err}");
+    assert!(err.contains("did you mean `macros`?"), "{err}");
+
+    // nothing is close enough
+    let err = read_err("[frobnicate]\n");
+    assert!(err.contains("unknown field `frobnicate`"), "{err}");
+    assert!(!err.contains("did you mean"), "{err}");
+
+    #[cfg(feature = "config-macros")]
+    {
+        let err = read_err("[macros]\ndatabase_url_vars = \"FOO_DATABASE_URL\"\n");
+        assert!(err.contains("did you mean `database_url_var`?"), "{err}");
+        assert!(err.contains("in `macros`"), "{err}");
+
+        let err = read_err("[macros]\nrename_all = \"camelcase\"\n");
+        assert!(err.contains("unknown variant `camelcase`"), "{err}");
+        assert!(err.contains("did you mean `camelCase`?"), "{err}");
+    }
+}
+
+#[test]
+fn test_edit_distance() {
+    assert_eq!(edit_distance("macros", "macros"), 0);
+    assert_eq!(edit_distance("marcos", "macros"), 2);
+    assert_eq!(edit_distance("database_url_vars", "database_url_var"), 1);
+    assert_eq!(edit_distance("", "pool"), 4);
+    assert_eq!(edit_distance("migrate", ""), 7);
+}
+
+#[test]
+#[cfg(feature = "config-macros")]
+fn test_read_from_path_is_uncached() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sqlx.toml");
+
+    std::fs::write(&path, "[macros]\ndatabase_url_var = \"FOO_DATABASE_URL\"\n").unwrap();
+
+    let cached = Config::try_get_with_path(&path).unwrap();
+
+    std::fs::write(&path, "[macros]\ndatabase_url_var = \"BAR_DATABASE_URL\"\n").unwrap();
+
+    let read = Config::read_from_path(&path).unwrap();
+    assert_eq!(
+        read.macros.database_url_var.as_deref(),
+        Some(&["BAR_DATABASE_URL".to_string()][..])
+    );
+
+    // the cached config is left alone
+    assert!(std::ptr::eq(
+        Config::try_get_with_path(&path).unwrap(),
+        cached
+    ));
+    assert_eq!(
+        cached.macros.database_url_var.as_deref(),
+        Some(&["FOO_DATABASE_URL".to_string()][..])
+    );
+
+    assert!(Config::read_from_path(dir.path().join("missing.toml"))
+        .unwrap_err()
+        .is_missing());
+}
+
+#[test]
+#[cfg(all(feature = "config-pool", feature = "any"))]
+fn test_pool_options_from_config() {
+    use crate::pool::PoolOptions;
+    use std::time::Duration;
+
+    let config: Config = toml::from_str(
+        "[pool]\nmax_connections = 50\nacquire_timeout_secs = 5\nidle_timeout_secs = 60\n",
+    )
+    .unwrap();
+
+    let options = PoolOptions::<crate::any::Any>::from_config(&config.pool);
+
+    assert_eq!(options.get_max_connections(), 50);
+    assert_eq!(options.get_acquire_timeout(), Duration::from_secs(5));
+    assert_eq!(options.get_idle_timeout(), Some(Duration::from_secs(60)));
+
+    // unset keys keep the defaults
+    assert_eq!(options.get_min_connections(), 0);
+    assert_eq!(
+        options.get_max_lifetime(),
+        Some(Duration::from_secs(30 * 60))
+    );
+    assert!(options.get_test_before_acquire());
+
+    // explicit calls take precedence
+    let options = options.max_connections(5).idle_timeout(None);
+
+    assert_eq!(options.get_max_connections(), 5);
+    assert_eq!(options.get_idle_timeout(), None);
+}
+
+#[test]
+fn test_try_get_defaults_if_missing() {
+    let dir = tempfile::tempdir().unwrap();
+
+    // no other test reads the config through `CARGO_MANIFEST_DIR`
+    std::env::set_var("CARGO_MANIFEST_DIR", dir.path());
+
+    let config = Config::try_get().unwrap();
+    assert_eq!(format!("{config:?}"), format!("{:?}", Config::default()));
+    assert!(std::ptr::eq(config, Config::get()));
+
+    // the path-based methods still report the missing file
+    assert!(Config::try_get_with_path(&dir.path().join("sqlx.toml"))
+        .unwrap_err()
+        .is_missing());
+
+    // a file that exists but is malformed is still an error
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("sqlx.toml"), "[macros\n").unwrap();
+    std::env::set_var("CARGO_MANIFEST_DIR", dir.path());
+
+    assert!(matches!(Config::try_get(), Err(ConfigError::Parse { .. })));
+}
@@ -0,0 +1,88 @@
+/// Configuration for connection pools, applied with `PoolOptions::from_config()`.
+///
+/// Unlike the other sections, this is only used at runtime, and only if the application reads
+/// `sqlx.toml` itself, e.g. with `Config::read_from_path()`, and passes this section to
+/// `PoolOptions::from_config()`. Unset keys keep the defaults of `PoolOptions::new()`.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The maximum number of connections the pool should maintain.
+    ///
+    /// See `PoolOptions::max_connections()`.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [pool]
+    /// max_connections = 50
+    /// ```
+    pub max_connections: Option<u32>,
+
+    /// The minimum number of connections the pool should try to maintain.
+    ///
+    /// See `PoolOptions::min_connections()`.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [pool]
+    /// min_connections = 5
+    /// ```
+    pub min_connections: Option<u32>,
+
+    /// The maximum time to spend waiting for a connection, in seconds.
+    ///
+    /// See `PoolOptions::acquire_timeout()`.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [pool]
+    /// acquire_timeout_secs = 10
+    /// ```
+    pub acquire_timeout_secs: Option<u64>,
+
+    /// The maximum idle duration of a connection before it is closed, in seconds.
+    ///
+    /// See `PoolOptions::idle_timeout()`. There is no way to disable the timeout from here;
+    /// call `idle_timeout(None)` on the returned `PoolOptions` instead.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [pool]
+    /// idle_timeout_secs = 300
+    /// ```
+    pub idle_timeout_secs: Option<u64>,
+
+    /// The maximum lifetime of a connection before it is closed, in seconds.
+    ///
+    /// See `PoolOptions::max_lifetime()`. As with `idle_timeout_secs`, the limit can only be
+    /// disabled in code.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [pool]
+    /// max_lifetime_secs = 3600
+    /// ```
+    pub max_lifetime_secs: Option<u64>,
+
+    /// Whether to test a connection's health before returning it from `acquire()`.
+    ///
+    /// See `PoolOptions::test_before_acquire()`.
+    ///
+    /// #### Example
+    ///
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [pool]
+    /// test_before_acquire = false
+    /// ```
+    pub test_before_acquire: Option<bool>,
+}
@@ -108,6 +108,12 @@ pub trait Database: 'static + Sized + Send + Debug {
 
     /// The schemes for database URLs that should match this driver.
     const URL_SCHEMES: &'static [&'static str];
+
+    /// `true` if the driver applies [`Query::timeout()`](crate::query::Query::timeout) on the
+    /// server, so the database aborts the statement once the timeout passes.
+    ///
+    /// Otherwise, the timeout is enforced on the client with the async runtime's timer.
+    const SUPPORTS_STATEMENT_TIMEOUT: bool = false;
 }
 
 /// A [`Database`] that maintains a client-side cache of prepared statements.
@@ -1,8 +1,11 @@
 //! Provides [`Decode`] for decoding values from the database.
 
-use crate::database::Database;
-use crate::error::BoxDynError;
+use std::any::type_name;
 
+use crate::database::Database;
+use crate::error::{mismatched_types, BoxDynError, DecodeError};
+use crate::type_info::TypeInfo;
+use crate::types::Type;
 use crate::value::ValueRef;
 
 /// A type that can be decoded from the database.
@@ -61,6 +64,57 @@ use crate::value::ValueRef;
 pub trait Decode<'r, DB: Database>: Sized {
     /// Decode a new value of this type using a raw value from the database.
     fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError>;
+
+    /// Decode a new value of this type, first checking that the SQL type of the value is
+    /// [compatible][Type::compatible] with it.
+    ///
+    /// An incompatible value is a [`DecodeError::TypeMismatch`] naming both types, instead of
+    /// whatever `decode()` makes of the bytes. `NULL`s are not checked, as in
+    /// [`Row::try_get()`](crate::row::Row::try_get), which uses this.
+    fn decode_checked(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError>
+    where
+        Self: Type<DB>,
+    {
+        if !value.is_null() {
+            let ty = value.type_info();
+
+            if !ty.is_null() && !Self::compatible(&ty) {
+                return Err(mismatched_types::<DB, Self>(&ty));
+            }
+        }
+
+        Self::decode(value)
+    }
+}
+
+/// Decode `value` as `T`, adding the SQL type of the value to the error if that fails.
+///
+/// The error is a [`DecodeError::WithType`] wrapping the error from `T::decode()`, so it reads
+/// "error decoding SQL type `X` as Rust type `Y`: ...". An error that already is one is returned
+/// unchanged, so calling this from a `Decode` impl that is itself decoded with it doesn't
+/// wrap the error twice.
+///
+/// `Decode` impls delegating to other types should generally call `decode()` directly;
+/// this is meant for the outermost call.
+pub fn decode_with_type<'r, DB, T>(value: <DB as Database>::ValueRef<'r>) -> Result<T, BoxDynError>
+where
+    DB: Database,
+    T: Decode<'r, DB>,
+{
+    let sql_type = value.type_info().name().to_owned();
+
+    T::decode(value).map_err(|source| {
+        if matches!(source.downcast_ref(), Some(DecodeError::WithType { .. })) {
+            return source;
+        }
+
+        DecodeError::WithType {
+            rust_type: type_name::<T>(),
+            sql_type,
+            source,
+        }
+        .into()
+    })
 }
 
 // implement `Decode` for Option<T> for all SQL types
@@ -77,3 +131,71 @@ where
         }
     }
 }
+
+// implement `Decode` for Box<T> for all SQL types
+impl<'r, DB, T> Decode<'r, DB> for Box<T>
+where
+    DB: Database,
+    T: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        T::decode(value).map(Box::new)
+    }
+}
+
+#[test]
+#[cfg(feature = "any")]
+fn test_decode_with_type() {
+    use crate::any::{Any, AnyValueKind, AnyValueRef};
+
+    let value = || AnyValueRef {
+        kind: AnyValueKind::Text("foo".into()),
+    };
+
+    let err = decode_with_type::<Any, i32>(value()).unwrap_err();
+    assert!(
+        matches!(
+            err.downcast_ref(),
+            Some(DecodeError::WithType { rust_type: "i32", sql_type, .. }) if sql_type == "TEXT"
+        ),
+        "{err:?}"
+    );
+    assert!(
+        err.to_string()
+            .starts_with("error decoding SQL type `TEXT` as Rust type `i32`: "),
+        "{err}"
+    );
+
+    assert_eq!(decode_with_type::<Any, String>(value()).unwrap(), "foo");
+}
+
+#[test]
+#[cfg(feature = "any")]
+fn test_decode_checked() {
+    use crate::any::{Any, AnyTypeInfoKind, AnyValueKind, AnyValueRef};
+
+    let text = AnyValueRef {
+        kind: AnyValueKind::Text("foo".into()),
+    };
+    let err = <i32 as Decode<Any>>::decode_checked(text).unwrap_err();
+    assert!(
+        matches!(
+            err.downcast_ref(),
+            Some(DecodeError::TypeMismatch { rust_type: "i32", got, .. }) if got == "TEXT"
+        ),
+        "{err:?}"
+    );
+
+    let int = AnyValueRef {
+        kind: AnyValueKind::Integer(5),
+    };
+    assert_eq!(<i32 as Decode<Any>>::decode_checked(int).unwrap(), 5);
+
+    let null = AnyValueRef {
+        kind: AnyValueKind::Null(AnyTypeInfoKind::Text),
+    };
+    assert_eq!(
+        <Option<i32> as Decode<Any>>::decode_checked(null).unwrap(),
+        None
+    );
+}
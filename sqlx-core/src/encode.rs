@@ -54,6 +54,30 @@ pub trait Encode<'q, DB: Database> {
     }
 }
 
+// implement `Encode` for Box<T> for all SQL types
+impl<'q, T, DB: Database> Encode<'q, DB> for Box<T>
+where
+    T: Encode<'q, DB>,
+{
+    #[inline]
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        <T as Encode<DB>>::encode_by_ref(self, buf)
+    }
+
+    #[inline]
+    fn produces(&self) -> Option<DB::TypeInfo> {
+        (**self).produces()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        (**self).size_hint()
+    }
+}
+
 impl<'q, T, DB: Database> Encode<'q, DB> for &'_ T
 where
     T: Encode<'q, DB>,
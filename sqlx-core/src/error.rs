@@ -5,6 +5,7 @@ use std::borrow::Cow;
 use std::error::Error as StdError;
 use std::fmt::Display;
 use std::io;
+use std::time::Duration;
 
 use crate::database::Database;
 
@@ -22,9 +23,80 @@ pub type BoxDynError = Box<dyn StdError + 'static + Send + Sync>;
 ///
 /// Returned from [`Row::get`](crate::row::Row::get) if the value from the database is `NULL`,
 /// and you are not decoding into an `Option`.
+///
+/// This is the [`DecodeError::UnexpectedNull`] variant under its old name, so existing
+/// `if let Some(UnexpectedNullError) = source.downcast_ref()` checks keep matching.
+/// Use `DecodeError::UnexpectedNull` in new code.
+// NOTE: rustc doesn't warn about uses of a deprecated re-export yet (rust-lang/rust#30827).
+#[deprecated(note = "use `DecodeError::UnexpectedNull` instead")]
+pub use self::DecodeError::UnexpectedNull as UnexpectedNullError;
+
+/// The cause of a failure to decode a value, for errors with a known cause.
+///
+/// [`Decode`](crate::decode::Decode) still returns a [`BoxDynError`], so this is found by
+/// downcasting the `source` of [`Error::ColumnDecode`] or the error in [`Error::Decode`]:
+///
+/// ```rust,ignore
+/// match row.try_get::<i32, _>(0) {
+///     Err(sqlx::Error::ColumnDecode { source, .. })
+///         if matches!(source.downcast_ref(), Some(DecodeError::UnexpectedNull)) =>
+///     {
+///         // ...
+///     }
+///     // ...
+/// }
+/// ```
+///
+/// Errors from elsewhere, like a third-party parser, are passed through as-is, so a failed
+/// downcast doesn't mean the cause is unknown to the decode implementation.
 #[derive(thiserror::Error, Debug)]
-#[error("unexpected null; try decoding as an `Option`")]
-pub struct UnexpectedNullError;
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The value was `NULL` and the Rust type is not an `Option`.
+    #[error("unexpected null; try decoding as an `Option`")]
+    UnexpectedNull,
+
+    /// The SQL type of the value is not compatible with the Rust type.
+    #[error(
+        "mismatched types; Rust type `{rust_type}` (as SQL type `{expected}`) is not compatible with SQL type `{got}`"
+    )]
+    TypeMismatch {
+        /// The name of the Rust type being decoded.
+        rust_type: &'static str,
+        /// The SQL type the Rust type maps to.
+        expected: String,
+        /// The SQL type of the value.
+        got: String,
+    },
+
+    /// The value could not be parsed into the Rust type.
+    #[error("error parsing value: {source}")]
+    Parse {
+        #[source]
+        source: BoxDynError,
+    },
+
+    /// Decoding the value failed, with the SQL type of the value added by
+    /// [`decode_with_type()`](crate::decode::decode_with_type).
+    #[error("error decoding SQL type `{sql_type}` as Rust type `{rust_type}`: {source}")]
+    WithType {
+        /// The name of the Rust type being decoded.
+        rust_type: &'static str,
+        /// The SQL type of the value.
+        sql_type: String,
+        #[source]
+        source: BoxDynError,
+    },
+
+    /// The value does not fit in the Rust type.
+    #[error("value {value} does not fit in target type {target}")]
+    OutOfRange {
+        /// The value, as printed by the decode implementation.
+        value: String,
+        /// The name of the Rust type being decoded.
+        target: &'static str,
+    },
+}
 
 /// Represents all the ways a method can fail within SQLx.
 #[derive(Debug, thiserror::Error)]
@@ -104,6 +176,12 @@ pub enum Error {
     #[error("attempted to acquire a connection on a closed pool")]
     PoolClosed,
 
+    /// A query did not complete within the timeout set by [`Query::timeout`].
+    ///
+    /// [`Query::timeout`]: crate::query::Query::timeout
+    #[error("query timed out after {0:?}")]
+    QueryTimedOut(Duration),
+
     /// A background worker has crashed.
     #[error("attempted to communicate with a crashed background worker")]
     WorkerCrashed,
@@ -155,12 +233,11 @@ impl Error {
 
 pub fn mismatched_types<DB: Database, T: Type<DB>>(ty: &DB::TypeInfo) -> BoxDynError {
     // TODO: `#name` only produces `TINYINT` but perhaps we want to show `TINYINT(1)`
-    format!(
-        "mismatched types; Rust type `{}` (as SQL type `{}`) is not compatible with SQL type `{}`",
-        type_name::<T>(),
-        T::type_info().name(),
-        ty.name()
-    )
+    DecodeError::TypeMismatch {
+        rust_type: type_name::<T>(),
+        expected: T::type_info().name().to_owned(),
+        got: ty.name().to_owned(),
+    }
     .into()
 }
 
@@ -7,6 +7,7 @@ use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::{future, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use std::fmt::Debug;
+use std::time::Duration;
 
 /// A type that contains or can provide a database
 /// connection to use for executing queries against the database.
@@ -208,6 +209,13 @@ pub trait Execute<'q, DB: Database>: Send + Sized {
 
     /// Returns `true` if the statement should be cached.
     fn persistent(&self) -> bool;
+
+    /// The timeout set with [`Query::timeout()`](crate::query::Query::timeout), if any.
+    ///
+    /// Drivers with [`Database::SUPPORTS_STATEMENT_TIMEOUT`] apply this on the server.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
 }
 
 // NOTE: `Execute` is explicitly not implemented for String and &String to make it slightly more
@@ -62,7 +62,10 @@ pub mod column;
 #[macro_use]
 pub mod statement;
 
+pub mod codec;
 pub mod common;
+#[cfg(feature = "config")]
+pub mod config;
 pub mod database;
 pub mod describe;
 pub mod executor;
@@ -23,6 +23,8 @@ pub struct Migrator {
     pub locking: bool,
     #[doc(hidden)]
     pub no_tx: bool,
+    #[doc(hidden)]
+    pub table_name: Cow<'static, str>,
 }
 
 fn validate_applied_migrations(
@@ -51,6 +53,7 @@ impl Migrator {
         ignore_missing: false,
         no_tx: false,
         locking: true,
+        table_name: Cow::Borrowed("_sqlx_migrations"),
     };
 
     /// Creates a new instance with the given source.
@@ -100,6 +103,22 @@ impl Migrator {
         self
     }
 
+    /// Specify the table recording the applied migrations. Defaults to `_sqlx_migrations`.
+    ///
+    /// The name is inserted into the SQL as-is, so it may be qualified with a schema
+    /// (e.g. `"app._migrations"`) but must be quoted if it needs quoting.
+    ///
+    /// `migrate!()` sets this from `migrate.table_name` in `sqlx.toml`, if set.
+    pub fn set_table_name(&mut self, table_name: impl Into<Cow<'static, str>>) -> &Self {
+        self.table_name = table_name.into();
+        self
+    }
+
+    /// The table recording the applied migrations.
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
     /// Get an iterator over all known migrations.
     pub fn iter(&self) -> slice::Iter<'_, Migration> {
         self.migrations.iter()
@@ -150,14 +169,14 @@ impl Migrator {
 
         // creates [_migrations] table only if needed
         // eventually this will likely migrate previous versions of the table
-        conn.ensure_migrations_table().await?;
+        conn.ensure_migrations_table(&self.table_name).await?;
 
-        let version = conn.dirty_version().await?;
+        let version = conn.dirty_version(&self.table_name).await?;
         if let Some(version) = version {
             return Err(MigrateError::Dirty(version));
         }
 
-        let applied_migrations = conn.list_applied_migrations().await?;
+        let applied_migrations = conn.list_applied_migrations(&self.table_name).await?;
         validate_applied_migrations(&applied_migrations, self)?;
 
         let applied_migrations: HashMap<_, _> = applied_migrations
@@ -177,7 +196,7 @@ impl Migrator {
                     }
                 }
                 None => {
-                    conn.apply(migration).await?;
+                    conn.apply(&self.table_name, migration).await?;
                 }
             }
         }
@@ -222,14 +241,14 @@ impl Migrator {
 
         // creates [_migrations] table only if needed
         // eventually this will likely migrate previous versions of the table
-        conn.ensure_migrations_table().await?;
+        conn.ensure_migrations_table(&self.table_name).await?;
 
-        let version = conn.dirty_version().await?;
+        let version = conn.dirty_version(&self.table_name).await?;
         if let Some(version) = version {
             return Err(MigrateError::Dirty(version));
         }
 
-        let applied_migrations = conn.list_applied_migrations().await?;
+        let applied_migrations = conn.list_applied_migrations(&self.table_name).await?;
         validate_applied_migrations(&applied_migrations, self)?;
 
         let applied_migrations: HashMap<_, _> = applied_migrations
@@ -244,7 +263,7 @@ impl Migrator {
             .filter(|m| applied_migrations.contains_key(&m.version))
             .filter(|m| m.version > target)
         {
-            conn.revert(migration).await?;
+            conn.revert(&self.table_name, migration).await?;
         }
 
         // unlock the migrator to allow other migrators to run
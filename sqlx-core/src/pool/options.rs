@@ -165,6 +165,43 @@ impl<DB: Database> PoolOptions<DB> {
         }
     }
 
+    /// Returns the defaults of [`Self::new()`], overridden by any values set in the `[pool]`
+    /// section of `sqlx.toml`.
+    ///
+    /// Builder methods called on the result take precedence, as they're applied afterwards.
+    /// SQLx doesn't read `sqlx.toml` at runtime on its own; load it with
+    /// [`Config::read_from_path()`][crate::config::Config::read_from_path] first.
+    #[cfg(feature = "config-pool")]
+    pub fn from_config(config: &crate::config::pool::Config) -> Self {
+        let mut options = Self::new();
+
+        if let Some(max) = config.max_connections {
+            options = options.max_connections(max);
+        }
+
+        if let Some(min) = config.min_connections {
+            options = options.min_connections(min);
+        }
+
+        if let Some(secs) = config.acquire_timeout_secs {
+            options = options.acquire_timeout(Duration::from_secs(secs));
+        }
+
+        if let Some(secs) = config.idle_timeout_secs {
+            options = options.idle_timeout(Duration::from_secs(secs));
+        }
+
+        if let Some(secs) = config.max_lifetime_secs {
+            options = options.max_lifetime(Duration::from_secs(secs));
+        }
+
+        if let Some(test) = config.test_before_acquire {
+            options = options.test_before_acquire(test);
+        }
+
+        options
+    }
+
     /// Set the maximum number of connections that this pool should maintain.
     ///
     /// Be mindful of the connection limits for your database as well as other applications
@@ -1,4 +1,6 @@
+use std::future::Future;
 use std::marker::PhantomData;
+use std::time::Duration;
 
 use either::Either;
 use futures_core::stream::BoxStream;
@@ -19,6 +21,7 @@ pub struct Query<'q, DB: Database, A> {
     pub(crate) arguments: Option<Result<A, BoxDynError>>,
     pub(crate) database: PhantomData<DB>,
     pub(crate) persistent: bool,
+    pub(crate) timeout: Option<Duration>,
 }
 
 /// A single SQL query that will map its results to an owned Rust type.
@@ -70,6 +73,11 @@ where
     fn persistent(&self) -> bool {
         self.persistent
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
 }
 
 impl<'q, DB: Database> Query<'q, DB, <DB as Database>::Arguments<'q>> {
@@ -140,6 +148,44 @@ where
     }
 }
 
+impl<'q, DB: Database, A> Query<'q, DB, A> {
+    /// Fail with [`Error::QueryTimedOut`] if the query takes longer than `timeout` to complete.
+    ///
+    /// ### Postgres
+    /// The statement runs with `statement_timeout` set to `timeout`, so the server aborts it once
+    /// the timeout passes. The previous `statement_timeout` is put back afterwards. This costs two
+    /// extra round trips, and it applies to every way of executing the query, including `fetch()`.
+    ///
+    /// ### Other Drivers
+    /// The deadline is enforced on the client with the async runtime's timer, and applies to
+    /// [`execute`][Self::execute], [`fetch_all`][Self::fetch_all], [`fetch_one`][Self::fetch_one]
+    /// and [`fetch_optional`][Self::fetch_optional]. Streams returned by `fetch()` are not affected.
+    ///
+    /// Giving up on the query does not stop the database from executing it. To have the server
+    /// abort the statement as well, also set a server-side limit such as `max_execution_time`
+    /// in MySQL.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+/// Await `fut`, failing with [`Error::QueryTimedOut`] if `timeout` is set and elapses first.
+///
+/// Drivers with [`Database::SUPPORTS_STATEMENT_TIMEOUT`] get the timeout through
+/// [`Execute::timeout()`] and enforce it on the server instead.
+pub(crate) async fn with_timeout<DB: Database, T>(
+    timeout: Option<Duration>,
+    fut: impl Future<Output = Result<T, Error>>,
+) -> Result<T, Error> {
+    match timeout {
+        Some(timeout) if !DB::SUPPORTS_STATEMENT_TIMEOUT => crate::rt::timeout(timeout, fut)
+            .await
+            .map_err(|_| Error::QueryTimedOut(timeout))?,
+        _ => fut.await,
+    }
+}
+
 impl<'q, DB, A: Send> Query<'q, DB, A>
 where
     DB: Database,
@@ -187,7 +233,7 @@ where
         A: 'e,
         E: Executor<'c, Database = DB>,
     {
-        executor.execute(self).await
+        with_timeout::<DB, _>(self.timeout, executor.execute(self)).await
     }
 
     /// Execute multiple queries and return the rows affected from each query, in a stream.
@@ -249,7 +295,7 @@ where
         A: 'e,
         E: Executor<'c, Database = DB>,
     {
-        executor.fetch_all(self).await
+        with_timeout::<DB, _>(self.timeout, executor.fetch_all(self)).await
     }
 
     /// Execute the query, returning the first row or [`Error::RowNotFound`] otherwise.
@@ -271,7 +317,7 @@ where
         A: 'e,
         E: Executor<'c, Database = DB>,
     {
-        executor.fetch_one(self).await
+        with_timeout::<DB, _>(self.timeout, executor.fetch_one(self)).await
     }
 
     /// Execute the query, returning the first row or `None` otherwise.
@@ -293,7 +339,7 @@ where
         A: 'e,
         E: Executor<'c, Database = DB>,
     {
-        executor.fetch_optional(self).await
+        with_timeout::<DB, _>(self.timeout, executor.fetch_optional(self)).await
     }
 }
 
@@ -321,6 +367,11 @@ where
     fn persistent(&self) -> bool {
         self.inner.arguments.is_some()
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout
+    }
 }
 
 impl<'q, DB, F, O, A> Map<'q, DB, F, A>
@@ -368,6 +419,14 @@ where
         }
     }
 
+    /// Fail with [`Error::QueryTimedOut`] if the query takes longer than `timeout` to complete.
+    ///
+    /// See [`Query::timeout`] for details.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.timeout(timeout);
+        self
+    }
+
     /// Execute the query and return the generated results as a stream.
     pub fn fetch<'e, 'c: 'e, E>(self, executor: E) -> BoxStream<'e, Result<O, Error>>
     where
@@ -435,7 +494,7 @@ where
         F: 'e,
         O: 'e,
     {
-        self.fetch(executor).try_collect().await
+        with_timeout::<DB, _>(self.inner.timeout, self.fetch(executor).try_collect()).await
     }
 
     /// Execute the query, returning the first row or [`Error::RowNotFound`] otherwise.
@@ -486,7 +545,8 @@ where
         F: 'e,
         O: 'e,
     {
-        let row = executor.fetch_optional(self.inner).await?;
+        let timeout = self.inner.timeout;
+        let row = with_timeout::<DB, _>(timeout, executor.fetch_optional(self.inner)).await?;
 
         if let Some(row) = row {
             (self.mapper)(row).map(Some)
@@ -508,6 +568,7 @@ where
         arguments: Some(Ok(Default::default())),
         statement: Either::Right(statement),
         persistent: true,
+        timeout: None,
     }
 }
 
@@ -525,6 +586,7 @@ where
         arguments: Some(Ok(arguments)),
         statement: Either::Right(statement),
         persistent: true,
+        timeout: None,
     }
 }
 
@@ -661,6 +723,7 @@ where
         arguments: Some(Ok(Default::default())),
         statement: Either::Left(sql),
         persistent: true,
+        timeout: None,
     }
 }
 
@@ -689,5 +752,6 @@ where
         arguments: Some(arguments),
         statement: Either::Left(sql),
         persistent: true,
+        timeout: None,
     }
 }
@@ -1,4 +1,5 @@
 use std::marker::PhantomData;
+use std::time::Duration;
 
 use either::Either;
 use futures_core::stream::BoxStream;
@@ -10,7 +11,9 @@ use crate::encode::Encode;
 use crate::error::{BoxDynError, Error};
 use crate::executor::{Execute, Executor};
 use crate::from_row::FromRow;
-use crate::query::{query, query_statement, query_statement_with, query_with_result, Query};
+use crate::query::{
+    query, query_statement, query_statement_with, query_with_result, with_timeout, Query,
+};
 use crate::types::Type;
 
 /// A single SQL query as a prepared statement, mapping results using [`FromRow`].
@@ -45,6 +48,11 @@ where
     fn persistent(&self) -> bool {
         self.inner.persistent()
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout
+    }
 }
 
 impl<'q, DB: Database, O> QueryAs<'q, DB, O, <DB as Database>::Arguments<'q>> {
@@ -85,6 +93,14 @@ where
     A: 'q + IntoArguments<'q, DB>,
     O: Send + Unpin + for<'r> FromRow<'r, DB::Row>,
 {
+    /// Fail with [`Error::QueryTimedOut`] if the query takes longer than `timeout` to complete.
+    ///
+    /// See [`Query::timeout`] for details.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.timeout(timeout);
+        self
+    }
+
     /// Execute the query and return the generated results as a stream.
     pub fn fetch<'e, 'c: 'e, E>(self, executor: E) -> BoxStream<'e, Result<O, Error>>
     where
@@ -142,7 +158,7 @@ where
         O: 'e,
         A: 'e,
     {
-        self.fetch(executor).try_collect().await
+        with_timeout::<DB, _>(self.inner.timeout, self.fetch(executor).try_collect()).await
     }
 
     /// Execute the query, returning the first row or [`Error::RowNotFound`] otherwise.
@@ -190,7 +206,8 @@ where
         O: 'e,
         A: 'e,
     {
-        let row = executor.fetch_optional(self.inner).await?;
+        let timeout = self.inner.timeout;
+        let row = with_timeout::<DB, _>(timeout, executor.fetch_optional(self.inner)).await?;
         if let Some(row) = row {
             O::from_row(&row).map(Some)
         } else {
@@ -453,6 +453,7 @@ where
             arguments: self.arguments.take().map(Ok),
             database: PhantomData,
             persistent: true,
+            timeout: None,
         }
     }
 
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use either::Either;
 use futures_core::stream::BoxStream;
 use futures_util::{StreamExt, TryFutureExt, TryStreamExt};
@@ -42,6 +44,11 @@ where
     fn persistent(&self) -> bool {
         Execute::persistent(&self.inner)
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        Execute::timeout(&self.inner)
+    }
 }
 
 impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as Database>::Arguments<'q>> {
@@ -83,6 +90,14 @@ where
     A: 'q + IntoArguments<'q, DB>,
     (O,): Send + Unpin + for<'r> FromRow<'r, DB::Row>,
 {
+    /// Fail with [`Error::QueryTimedOut`] if the query takes longer than `timeout` to complete.
+    ///
+    /// See [`Query::timeout`][crate::query::Query::timeout] for details.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.timeout(timeout);
+        self
+    }
+
     /// Execute the query and return the generated results as a stream.
     #[inline]
     pub fn fetch<'e, 'c: 'e, E>(self, executor: E) -> BoxStream<'e, Result<O, Error>>
@@ -135,9 +150,8 @@ where
         A: 'e,
     {
         self.inner
-            .fetch(executor)
-            .map_ok(|it| it.0)
-            .try_collect()
+            .fetch_all(executor)
+            .map_ok(|rows| rows.into_iter().map(|it| it.0).collect())
             .await
     }
 
@@ -1,11 +1,9 @@
 use crate::column::ColumnIndex;
 use crate::database::Database;
 use crate::decode::Decode;
-use crate::error::{mismatched_types, Error};
+use crate::error::Error;
 
-use crate::type_info::TypeInfo;
 use crate::types::Type;
-use crate::value::ValueRef;
 
 /// Represents a single row from the database.
 ///
@@ -114,18 +112,7 @@ pub trait Row: Unpin + Send + Sync + 'static {
     {
         let value = self.try_get_raw(&index)?;
 
-        if !value.is_null() {
-            let ty = value.type_info();
-
-            if !ty.is_null() && !T::compatible(&ty) {
-                return Err(Error::ColumnDecode {
-                    index: format!("{index:?}"),
-                    source: mismatched_types::<Self::Database, T>(&ty),
-                });
-            }
-        }
-
-        T::decode(value).map_err(|source| Error::ColumnDecode {
+        T::decode_checked(value).map_err(|source| Error::ColumnDecode {
             index: format!("{index:?}"),
             source,
         })
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 
 use serde::{Deserialize, Serialize};
@@ -51,6 +52,9 @@ use crate::types::Type;
 ///   dewey_decimal: sqlx::types::Json<HashMap<String, Book>>
 /// }
 /// ```
+///
+/// A `HashMap` with deserializable keys and values can also be decoded directly, without the
+/// `Json` wrapper.
 #[derive(
     Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
 )]
@@ -153,6 +157,44 @@ where
     }
 }
 
+/// A JSON object decoded directly into a map, without the [`Json`] wrapper.
+impl<K, V, S, DB> Type<DB> for HashMap<K, V, S>
+where
+    Json<Self>: Type<DB>,
+    DB: Database,
+{
+    fn type_info() -> DB::TypeInfo {
+        <Json<Self> as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <Json<Self> as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, K, V, S, DB> Encode<'q, DB> for HashMap<K, V, S>
+where
+    for<'a> Json<&'a Self>: Encode<'q, DB>,
+    DB: Database,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        <Json<&Self> as Encode<'q, DB>>::encode(Json(self), buf)
+    }
+}
+
+impl<'r, K, V, S, DB> Decode<'r, DB> for HashMap<K, V, S>
+where
+    Json<Self>: Decode<'r, DB>,
+    DB: Database,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        <Json<Self> as Decode<DB>>::decode(value).map(|item| item.0)
+    }
+}
+
 impl<DB> Type<DB> for JsonRawValue
 where
     for<'a> Json<&'a Self>: Type<DB>,
@@ -178,3 +220,46 @@ where
         <Json<Self> as Decode<DB>>::decode(value).map(|item| item.0)
     }
 }
+
+impl<DB> Type<DB> for Box<JsonRawValue>
+where
+    for<'a> Json<&'a Self>: Type<DB>,
+    DB: Database,
+{
+    fn type_info() -> DB::TypeInfo {
+        <Json<&Self> as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <Json<&Self> as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB> Encode<'q, DB> for Box<JsonRawValue>
+where
+    for<'a> Json<&'a Self>: Encode<'q, DB>,
+    DB: Database,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        <Json<&Self> as Encode<'q, DB>>::encode(Json(self), buf)
+    }
+}
+
+/// Decodes the JSON text without parsing it into a structure, for forwarding as-is.
+///
+/// Unlike `&RawValue`, this doesn't borrow from the row.
+///
+/// The text is still checked to be valid JSON, as `RawValue` guarantees, so
+/// decoding a malformed value (e.g. from a SQLite `TEXT` column) is an error.
+impl<'r, DB> Decode<'r, DB> for Box<JsonRawValue>
+where
+    Json<Self>: Decode<'r, DB>,
+    DB: Database,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        <Json<Self> as Decode<DB>>::decode(value).map(|item| item.0)
+    }
+}
@@ -20,6 +20,7 @@
 use crate::database::Database;
 use crate::type_info::TypeInfo;
 
+mod narrow;
 mod non_zero;
 
 #[cfg(feature = "bstr")]
@@ -32,6 +33,13 @@ mod json;
 
 mod text;
 
+#[cfg(feature = "semver")]
+#[cfg_attr(docsrs, doc(cfg(feature = "semver")))]
+pub mod semver;
+
+#[cfg(feature = "url")]
+mod url;
+
 #[cfg(feature = "uuid")]
 #[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
 #[doc(no_inline)]
@@ -81,8 +89,14 @@ pub mod mac_address {
     pub use mac_address::MacAddress;
 }
 
+#[cfg(feature = "url")]
+#[cfg_attr(docsrs, doc(cfg(feature = "url")))]
+#[doc(no_inline)]
+pub use ::url::Url;
+
 #[cfg(feature = "json")]
 pub use json::{Json, JsonRawValue, JsonValue};
+pub use narrow::Narrow;
 pub use text::Text;
 
 /// Indicates that a SQL type is supported for a database.
@@ -238,3 +252,16 @@ impl<T: Type<DB>, DB: Database> Type<DB> for Option<T> {
         ty.is_null() || <T as Type<DB>>::compatible(ty)
     }
 }
+
+// for boxes, the underlying SQL type is identical
+//
+// `Box<str>`, `Box<[u8]>` and `Box<JsonRawValue>` have their own impls since `T` is unsized.
+impl<T: Type<DB>, DB: Database> Type<DB> for Box<T> {
+    fn type_info() -> DB::TypeInfo {
+        <T as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <T as Type<DB>>::compatible(ty)
+    }
+}
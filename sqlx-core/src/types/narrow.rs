@@ -0,0 +1,94 @@
+use std::any::type_name;
+use std::ops::{Deref, DerefMut};
+
+use crate::database::Database;
+use crate::decode::Decode;
+use crate::error::{BoxDynError, DecodeError};
+use crate::types::Type;
+
+/// Decode a SQL integer of any width into a narrower Rust integer type, checking for overflow.
+///
+/// The value is decoded as an `i64` and then converted with [`TryFrom`]. If it does not fit in
+/// `T`, decoding fails with an error instead of silently truncating or wrapping.
+///
+/// This is meant for columns that are declared wider than the values they actually hold,
+/// such as a small enum discriminant stored in a `BIGINT`, where the narrowing is intentional.
+/// Any SQL integer type accepted by `i16`, `i32` or `i64` is accepted by this adapter.
+///
+/// Unsigned 64-bit values larger than `i64::MAX` (MySQL's `BIGINT UNSIGNED`) are not supported.
+///
+/// ### Example
+/// ```rust,no_run
+/// use sqlx::types::Narrow;
+///
+/// # async fn example(conn: &mut sqlx::SqliteConnection) -> sqlx::Result<()> {
+/// // `kind` is a `BIGINT` column, but only ever holds values from 0 to 3.
+/// let kind: Narrow<u8> = sqlx::query_scalar("SELECT kind FROM items WHERE id = ?")
+///     .bind(1_i64)
+///     .fetch_one(conn)
+///     .await?;
+///
+/// let kind: u8 = kind.into_inner();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Narrow<T>(pub T);
+
+impl<T> Narrow<T> {
+    /// Extract the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Narrow<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Narrow<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, DB> Type<DB> for Narrow<T>
+where
+    DB: Database,
+    i16: Type<DB>,
+    i32: Type<DB>,
+    i64: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <i64 as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <i64 as Type<DB>>::compatible(ty)
+            || <i32 as Type<DB>>::compatible(ty)
+            || <i16 as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'r, T, DB> Decode<'r, DB> for Narrow<T>
+where
+    DB: Database,
+    T: TryFrom<i64>,
+    i64: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let int = <i64 as Decode<'r, DB>>::decode(value)?;
+
+        T::try_from(int).map(Narrow).map_err(|_| {
+            DecodeError::OutOfRange {
+                value: int.to_string(),
+                target: type_name::<T>(),
+            }
+            .into()
+        })
+    }
+}
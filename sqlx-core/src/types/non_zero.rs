@@ -10,6 +10,7 @@ use std::num::{
 use crate::database::Database;
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
+use crate::error::DecodeError;
 use crate::types::Type;
 
 macro_rules! impl_non_zero {
@@ -56,9 +57,15 @@ macro_rules! impl_non_zero {
         {
             fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, crate::error::BoxDynError> {
                 let int = <$int as Decode<'r, DB>>::decode(value)?;
-                let non_zero = Self::try_from(int)?;
 
-                Ok(non_zero)
+                // `TryFrom`'s error only says "out of range integral type conversion attempted"
+                Self::new(int).ok_or_else(|| {
+                    DecodeError::OutOfRange {
+                        value: "0".into(),
+                        target: stringify!($non_zero),
+                    }
+                    .into()
+                })
             }
         })*
     };
@@ -0,0 +1,52 @@
+//! Conversions between `semver` types and SQL types.
+use crate::database::Database;
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+
+#[doc(no_inline)]
+pub use ::semver::{Version, VersionReq};
+
+macro_rules! impl_text {
+    ($($ty:ty),*) => {
+        $(impl<DB> Type<DB> for $ty
+        where
+            DB: Database,
+            str: Type<DB>,
+        {
+            fn type_info() -> DB::TypeInfo {
+                <&str as Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <&str as Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<'r, DB> Decode<'r, DB> for $ty
+        where
+            DB: Database,
+            &'r str: Decode<'r, DB>,
+        {
+            fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                Ok(<&str as Decode<DB>>::decode(value)?.parse()?)
+            }
+        }
+
+        impl<'q, DB> Encode<'q, DB> for $ty
+        where
+            DB: Database,
+            String: Encode<'q, DB>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+            ) -> Result<IsNull, BoxDynError> {
+                <String as Encode<DB>>::encode(self.to_string(), buf)
+            }
+        })*
+    };
+}
+
+impl_text!(Version, VersionReq);
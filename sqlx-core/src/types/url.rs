@@ -0,0 +1,51 @@
+//! Conversions between `url::Url` and SQL types.
+use crate::database::Database;
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+
+use ::url::Url;
+
+impl<DB> Type<DB> for Url
+where
+    DB: Database,
+    str: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <&str as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <&str as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'r, DB> Decode<'r, DB> for Url
+where
+    DB: Database,
+    &'r str: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let text = <&str as Decode<DB>>::decode(value)?;
+
+        Ok(Url::parse(text)?)
+    }
+}
+
+impl<'q, DB> Encode<'q, DB> for Url
+where
+    DB: Database,
+    String: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        <String as Encode<DB>>::encode(self.as_str().to_owned(), buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.as_str().len()
+    }
+}
@@ -1,7 +1,6 @@
 use crate::database::Database;
 use crate::decode::Decode;
-use crate::error::{mismatched_types, Error};
-use crate::type_info::TypeInfo;
+use crate::error::Error;
 use crate::types::Type;
 use std::borrow::Cow;
 
@@ -64,15 +63,7 @@ pub trait Value {
     where
         T: Decode<'r, Self::Database> + Type<Self::Database>,
     {
-        if !self.is_null() {
-            let ty = self.type_info();
-
-            if !ty.is_null() && !T::compatible(&ty) {
-                return Err(Error::Decode(mismatched_types::<Self::Database, T>(&ty)));
-            }
-        }
-
-        self.try_decode_unchecked()
+        T::decode_checked(self.as_ref()).map_err(Error::Decode)
     }
 
     /// Decode this single value into the requested type.
@@ -8,6 +8,22 @@ macro_rules! impl_database_ext {
             const DATABASE_PATH: &'static str = stringify!($database);
             const ROW_PATH: &'static str = stringify!($row);
             impl_describe_blocking!($database, $($describe)?);
+
+            fn describe_fallback_blocking(
+                query: &str,
+                database_url: &str,
+            ) -> sqlx_core::Result<sqlx_core::describe::Describe<Self>> {
+                $crate::database::prepare_blocking::<$database>(query, database_url)
+            }
+
+            #[cfg(feature = "migrate")]
+            fn list_applied_migrations_blocking(
+                database_url: &str,
+                table_name: &str,
+            ) -> Result<Vec<sqlx_core::migrate::AppliedMigration>, sqlx_core::migrate::MigrateError>
+            {
+                $crate::database::list_applied_migrations_blocking::<$database>(database_url, table_name)
+            }
         }
     }
 }
@@ -8,6 +8,9 @@ use sqlx_core::connection::Connection;
 use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
+#[cfg(feature = "migrate")]
+use sqlx_core::migrate::{AppliedMigration, Migrate, MigrateError};
+use sqlx_core::statement::Statement;
 use sqlx_core::type_checking::TypeChecking;
 
 #[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
@@ -26,6 +29,68 @@ pub trait DatabaseExt: Database + TypeChecking {
     }
 
     fn describe_blocking(query: &str, database_url: &str) -> sqlx_core::Result<Describe<Self>>;
+
+    /// Describe `query` using only what the database reports from preparing it.
+    ///
+    /// Used when [`describe_blocking()`][Self::describe_blocking] fails and
+    /// `describe_fallback` is enabled. Output columns are reported with unknown nullability.
+    fn describe_fallback_blocking(
+        query: &str,
+        database_url: &str,
+    ) -> sqlx_core::Result<Describe<Self>>;
+
+    /// List the migrations recorded in `table_name`, for `macros.check_migrations`.
+    #[cfg(feature = "migrate")]
+    fn list_applied_migrations_blocking(
+        database_url: &str,
+        table_name: &str,
+    ) -> Result<Vec<AppliedMigration>, MigrateError>;
+}
+
+#[allow(dead_code)]
+pub fn prepare_blocking<DB: DatabaseExt>(
+    query: &str,
+    database_url: &str,
+) -> sqlx_core::Result<Describe<DB>>
+where
+    DB::Column: Clone,
+    for<'a> &'a mut DB::Connection: Executor<'a, Database = DB>,
+{
+    crate::block_on(async {
+        let mut conn = DB::Connection::connect(database_url).await?;
+
+        let describe = conn.prepare(query).await.map(|statement| Describe {
+            columns: statement.columns().to_vec(),
+            parameters: statement
+                .parameters()
+                .map(|params| params.map_left(<[_]>::to_vec)),
+            nullable: vec![None; statement.columns().len()],
+        });
+
+        conn.close().await?;
+
+        describe
+    })
+}
+
+#[cfg(feature = "migrate")]
+#[allow(dead_code)]
+pub fn list_applied_migrations_blocking<DB: DatabaseExt>(
+    database_url: &str,
+    table_name: &str,
+) -> Result<Vec<AppliedMigration>, MigrateError>
+where
+    DB::Connection: Migrate,
+{
+    crate::block_on(async {
+        let mut conn = DB::Connection::connect(database_url).await?;
+
+        let applied = conn.list_applied_migrations(table_name).await;
+
+        conn.close().await?;
+
+        applied
+    })
 }
 
 #[allow(dead_code)]
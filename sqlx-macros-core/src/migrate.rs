@@ -7,6 +7,7 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens, TokenStreamExt};
 use syn::LitStr;
 
+use sqlx_core::config::Config;
 use sqlx_core::migrate::{Migration, MigrationType};
 
 pub struct QuoteMigrationType(MigrationType);
@@ -85,6 +86,31 @@ pub fn expand_migrator_from_lit_dir(dir: LitStr) -> crate::Result<TokenStream> {
     expand_migrator_from_dir(&dir.value(), dir.span())
 }
 
+/// Expand `migrate!()` without an argument, using `migrate.migrations_dir` or `./migrations`.
+pub fn expand_migrator_from_config() -> crate::Result<TokenStream> {
+    let path =
+        crate::common::resolve_path(default_migrations_dir()?, proc_macro2::Span::call_site())?;
+
+    expand_migrator(&path)
+}
+
+/// The directory set by `migrate.migrations_dir` in `sqlx.toml`, or `./migrations`.
+pub(crate) fn default_migrations_dir() -> crate::Result<&'static Path> {
+    Ok(migrate_config()?
+        .migrations_dir
+        .as_deref()
+        .unwrap_or("./migrations".as_ref()))
+}
+
+fn migrate_config() -> crate::Result<&'static sqlx_core::config::migrate::Config> {
+    let config = Config::try_get().map_err(|e| match std::error::Error::source(&e) {
+        Some(source) => format!("failed to load sqlx config: {e}: {source}"),
+        None => format!("failed to load sqlx config: {e}"),
+    })?;
+
+    Ok(&config.migrate)
+}
+
 pub(crate) fn expand_migrator_from_dir(
     dir: &str,
     err_span: proc_macro2::Span,
@@ -119,11 +145,18 @@ pub(crate) fn expand_migrator(path: &Path) -> crate::Result<TokenStream> {
         proc_macro::tracked_path::path(path);
     }
 
+    let table_name = migrate_config()?.table_name.as_deref().map(|table_name| {
+        quote! {
+            table_name: ::std::borrow::Cow::Borrowed(#table_name),
+        }
+    });
+
     Ok(quote! {
         ::sqlx::migrate::Migrator {
             migrations: ::std::borrow::Cow::Borrowed(&[
                     #(#migrations),*
             ]),
+            #table_name
             ..::sqlx::migrate::Migrator::DEFAULT
         }
     })
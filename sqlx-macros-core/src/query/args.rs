@@ -1,7 +1,7 @@
 use crate::database::DatabaseExt;
 use crate::query::QueryMacroInput;
 use either::Either;
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote, quote_spanned};
 use sqlx_core::describe::Describe;
 use syn::spanned::Spanned;
@@ -26,9 +26,18 @@ pub fn quote_args<DB: DatabaseExt>(
         .collect::<Vec<_>>();
 
     let arg_name = &arg_names;
+
     let arg_expr = input.arg_exprs.iter().cloned().map(strip_wildcard);
 
+    // with `params = ..`, the arguments are accesses of its fields; the binding is spanned
+    // like they are so that they resolve to it
+    let params = input
+        .params
+        .as_ref()
+        .map(|params| quote_spanned!(params.span() => let params = &(#params);));
+
     let arg_bindings = quote! {
+        #params
         #(let #arg_name = &(#arg_expr);)*
     };
 
@@ -55,43 +64,23 @@ pub fn quote_args<DB: DatabaseExt>(
                         return Ok(quote!());
                     }
 
-                    let param_ty =
-                        DB::param_type_for_id(param_ty)
-                            .ok_or_else(|| {
-                                if let Some(feature_gate) = DB::get_feature_gate(param_ty) {
-                                    format!(
-                                        "optional sqlx feature `{}` required for type {} of param #{}",
-                                        feature_gate,
-                                        param_ty,
-                                        i + 1,
-                                    )
-                                } else {
-                                    format!("unsupported type {} for param #{}", param_ty, i + 1)
-                                }
-                            })?
-                            .parse::<TokenStream>()
-                            .map_err(|_| format!("Rust type mapping for {param_ty} not parsable"))?;
-
-                    Ok(quote_spanned!(expr.span() =>
-                        // this shouldn't actually run
-                        if false {
-                            use ::sqlx::ty_match::{WrapSameExt as _, MatchBorrowExt as _};
-
-                            // evaluate the expression only once in case it contains moves
-                            let expr = ::sqlx::ty_match::dupe_value(#name);
-
-                            // if `expr` is `Option<T>`, get `Option<$ty>`, otherwise `$ty`
-                            let ty_check = ::sqlx::ty_match::WrapSame::<#param_ty, _>::new(&expr).wrap_same();
-
-                            // if `expr` is `&str`, convert `String` to `&str`
-                            let (mut _ty_check, match_borrow) = ::sqlx::ty_match::MatchBorrow::new(ty_check, &expr);
-
-                            _ty_check = match_borrow.match_borrow();
-
-                            // this causes move-analysis to effectively ignore this block
-                            ::std::panic!();
-                        }
-                    ))
+                    let param_ty = DB::param_type_for_id(param_ty)
+                        .ok_or_else(|| {
+                            if let Some(feature_gate) = DB::get_feature_gate(param_ty) {
+                                format!(
+                                    "optional sqlx feature `{}` required for type {} of param #{}",
+                                    feature_gate,
+                                    param_ty,
+                                    i + 1,
+                                )
+                            } else {
+                                format!("unsupported type {} for param #{}", param_ty, i + 1)
+                            }
+                        })?
+                        .parse::<TokenStream>()
+                        .map_err(|_| format!("Rust type mapping for {param_ty} not parsable"))?;
+
+                    Ok(quote_type_check(name, expr, &param_ty))
                 })
                 .collect::<crate::Result<TokenStream>>()?
         }
@@ -114,7 +103,60 @@ pub fn quote_args<DB: DatabaseExt>(
     })
 }
 
-fn get_type_override(expr: &Expr) -> Option<&Type> {
+/// Check that `input` has `expected` arguments, `source` naming where the count came from
+/// if it wasn't from describing the query.
+///
+/// The error points to the first extra argument if there are too many, or the last argument
+/// (or the query if there are none) if there are too few.
+pub(super) fn check_arg_count(
+    input: &QueryMacroInput,
+    expected: usize,
+    source: Option<&str>,
+) -> crate::Result<()> {
+    let got = input.arg_exprs.len();
+
+    if expected == got {
+        return Ok(());
+    }
+
+    let message = match source {
+        Some(source) => format!("expected {expected} parameters, got {got} (from {source})"),
+        None => format!("expected {expected} parameters, got {got}"),
+    };
+
+    let error = match input.arg_exprs.get(expected).or(input.arg_exprs.last()) {
+        Some(expr) => syn::Error::new_spanned(expr, message),
+        None => syn::Error::new(input.src_span, message),
+    };
+
+    Err(error.into())
+}
+
+/// A block that fails to compile if the argument bound to `name` can't be encoded as `param_ty`.
+pub(super) fn quote_type_check(name: &Ident, expr: &Expr, param_ty: &TokenStream) -> TokenStream {
+    quote_spanned!(expr.span() =>
+        // this shouldn't actually run
+        if false {
+            use ::sqlx::ty_match::{WrapSameExt as _, MatchBorrowExt as _};
+
+            // evaluate the expression only once in case it contains moves
+            let expr = ::sqlx::ty_match::dupe_value(#name);
+
+            // if `expr` is `Option<T>`, get `Option<$ty>`, otherwise `$ty`
+            let ty_check = ::sqlx::ty_match::WrapSame::<#param_ty, _>::new(&expr).wrap_same();
+
+            // if `expr` is `&str`, convert `String` to `&str`
+            let (mut _ty_check, match_borrow) = ::sqlx::ty_match::MatchBorrow::new(ty_check, &expr);
+
+            _ty_check = match_borrow.match_borrow();
+
+            // this causes move-analysis to effectively ignore this block
+            ::std::panic!();
+        }
+    )
+}
+
+pub(super) fn get_type_override(expr: &Expr) -> Option<&Type> {
     match expr {
         Expr::Group(group) => get_type_override(&group.expr),
         Expr::Cast(cast) => Some(&cast.ty),
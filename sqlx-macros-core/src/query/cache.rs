@@ -0,0 +1,611 @@
+//! Storage for the query data used by the macros in offline mode.
+//!
+//! By default this is the `.sqlx` directory written by `cargo sqlx prepare`
+//! ([`FilesystemQueryCache`]), but a different backend may be installed with
+//! [`set_query_cache()`] by a proc-macro crate wrapping this one.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use once_cell::sync::OnceCell;
+
+pub use crate::query::data::{hash_string, normalize_sql, DynQueryData, QueryData};
+use crate::query::env;
+use crate::query::metadata::Metadata;
+
+/// A backend for saving and loading query data.
+///
+/// ### Thread Safety
+/// The same instance is shared by every macro invocation in the compiler process, which may
+/// expand macros on more than one thread, so implementations must synchronize access to any
+/// state they hold. Separate compiler processes (one per crate) each get their own instance;
+/// a backend shared between them, like the filesystem, must tolerate concurrent writes of the
+/// same entry.
+pub trait QueryCache: Send + Sync {
+    /// Load the data for the query with the given hash, if there is any.
+    ///
+    /// `sql` is the query itself; implementations should return an error if the data they
+    /// find under `hash` is for a different query.
+    fn load(&self, hash: &str, sql: &str) -> crate::Result<Option<DynQueryData>>;
+
+    /// Load the data for a query read by `query_file!()` or its variants from the file at
+    /// `file_path`, if there is any.
+    ///
+    /// Defaults to [`Self::load()`], ignoring `file_path`.
+    fn load_for_file(
+        &self,
+        hash: &str,
+        sql: &str,
+        file_path: &Path,
+    ) -> crate::Result<Option<DynQueryData>> {
+        let _ = file_path;
+        self.load(hash, sql)
+    }
+
+    /// Save the data for the query with the given hash.
+    ///
+    /// `data` is a JSON document in the format of `.sqlx/query-<hash>.json` files,
+    /// which deserializes to [`DynQueryData`].
+    ///
+    /// This is only called for queries that were described against a live database.
+    fn store(&self, hash: &str, data: &str) -> crate::Result<()>;
+}
+
+static QUERY_CACHE: OnceCell<Box<dyn QueryCache>> = OnceCell::new();
+
+/// Use `cache` instead of [`FilesystemQueryCache`] for the remainder of the compiler process.
+///
+/// This must be called before the first query macro is expanded. If a backend was already
+/// installed or used, `cache` is handed back.
+pub fn set_query_cache(cache: Box<dyn QueryCache>) -> Result<(), Box<dyn QueryCache>> {
+    QUERY_CACHE.set(cache)
+}
+
+/// The installed backend, or [`FilesystemQueryCache`] if [`set_query_cache()`] wasn't called.
+pub fn query_cache() -> &'static dyn QueryCache {
+    &**QUERY_CACHE.get_or_init(|| Box::new(FilesystemQueryCache))
+}
+
+/// The default backend, reading and writing `query-<hash>.json` files, or
+/// `query-<hash>.json.gz` files with `macros.offline_compression` enabled, or a single
+/// `queries.json` file with `macros.offline_single_file` enabled.
+///
+/// Data is loaded from the first of these that has it:
+///
+/// * `$SQLX_OFFLINE_DIR`, which may be a list of directories to check in order, separated
+///   like `PATH`: by `:` on Unix and by `;` on Windows
+/// * `macros.offline_dir` in `sqlx.toml`
+/// * `.sqlx` in the directory of the crate being compiled
+/// * for `query_file!()` and its variants, `.sqlx` in the directory of the query file
+/// * `.sqlx` in the workspace root
+///
+/// and saved to `$SQLX_OFFLINE_DIR` only, or its first directory if it's a list, which is set
+/// by `cargo sqlx prepare`. Nothing is saved if it is not set.
+///
+/// The file each query's data is loaded from is logged at the `debug` level with `tracing`,
+/// to help find out where stale data comes from.
+#[derive(Debug, Default)]
+pub struct FilesystemQueryCache;
+
+impl FilesystemQueryCache {
+    /// Load from the usual directories, checking `extra_dir` before the workspace root.
+    fn load_from(
+        &self,
+        hash: &str,
+        sql: &str,
+        extra_dir: Option<PathBuf>,
+    ) -> crate::Result<Option<DynQueryData>> {
+        // Check SQLX_OFFLINE_DIR, then the configured dir, then local .sqlx, then `extra_dir`,
+        // then workspace .sqlx.
+        let offline_dirs = env("SQLX_OFFLINE_DIR")
+            .map(|dirs| std::env::split_paths(&dirs).collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        let dirs = [
+            || {
+                let metadata = Metadata::get();
+                let dir = metadata.config?.offline_dir.as_ref()?;
+                Some(metadata.manifest_dir.join(dir))
+            },
+            || Some(Metadata::get().manifest_dir.join(".sqlx")),
+        ];
+
+        let data = load_from_dirs(
+            offline_dirs
+                .into_iter()
+                .chain(dirs.iter().filter_map(|dir| dir()))
+                .chain(extra_dir),
+            hash,
+            sql,
+        )?;
+
+        if data.is_some() {
+            return Ok(data);
+        }
+
+        // Only look up the workspace root if the query isn't found elsewhere, since it runs
+        // `cargo metadata`.
+        let workspace_dir = Metadata::get().workspace_root()?.join(".sqlx");
+
+        load_from_dirs(std::iter::once(workspace_dir), hash, sql)
+    }
+}
+
+impl QueryCache for FilesystemQueryCache {
+    fn load(&self, hash: &str, sql: &str) -> crate::Result<Option<DynQueryData>> {
+        self.load_from(hash, sql, None)
+    }
+
+    fn load_for_file(
+        &self,
+        hash: &str,
+        sql: &str,
+        file_path: &Path,
+    ) -> crate::Result<Option<DynQueryData>> {
+        self.load_from(hash, sql, file_path.parent().map(|dir| dir.join(".sqlx")))
+    }
+
+    fn store(&self, hash: &str, data: &str) -> crate::Result<()> {
+        // Only save query metadata if SQLX_OFFLINE_DIR is set manually or by `cargo sqlx prepare`.
+        // Note: in a cargo workspace this path is relative to the root.
+        // If it's a list, the first directory is the one to save to.
+        let Some(path) = env("SQLX_OFFLINE_DIR")
+            .ok()
+            .and_then(|dirs| std::env::split_paths(&dirs).next())
+        else {
+            return Ok(());
+        };
+
+        let dir = path.display();
+
+        match fs::metadata(&path) {
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    // Can't obtain information about .sqlx
+                    return Err(format!("{e}: {dir}").into());
+                }
+                // .sqlx doesn't exist.
+                Err(format!("sqlx offline path does not exist: {dir}").into())
+            }
+            Ok(meta) => {
+                if !meta.is_dir() {
+                    return Err(
+                        format!("sqlx offline path exists, but is not a directory: {dir}").into(),
+                    );
+                }
+
+                let config = Metadata::get().config;
+
+                // .sqlx exists and is a directory, store data.
+                if config.map_or(false, |config| config.offline_single_file) {
+                    write_merged_file(&path, hash, data)
+                } else {
+                    let compress = config.map_or(false, |config| config.offline_compression);
+
+                    write_data_file(&path, hash, data, compress)
+                }
+            }
+        }
+    }
+}
+
+/// Load the data from the first of `dirs` that has it.
+fn load_from_dirs(
+    dirs: impl Iterator<Item = PathBuf>,
+    hash: &str,
+    sql: &str,
+) -> crate::Result<Option<DynQueryData>> {
+    // Both are loaded regardless of `offline_compression`.
+    let filenames = [
+        format!("query-{hash}.json"),
+        format!("query-{hash}.json.gz"),
+    ];
+
+    for dir in dirs {
+        if let Some(data_file_path) = filenames
+            .iter()
+            .map(|filename| dir.join(filename))
+            .find(|path| path.exists())
+        {
+            tracing::debug!("loading query data for {hash} from {data_file_path:?}");
+
+            return DynQueryData::from_data_file(&data_file_path, sql).map(Some);
+        }
+
+        let merged_file_path = dir.join(MERGED_FILENAME);
+
+        if merged_file_path.exists() {
+            if let Some(data) = DynQueryData::from_merged_file(&merged_file_path, hash, sql)? {
+                tracing::debug!("loaded query data for {hash} from {merged_file_path:?}");
+
+                return Ok(Some(data));
+            }
+        }
+    }
+
+    tracing::debug!("no query data found for {hash}");
+
+    Ok(None)
+}
+
+/// Write the data for `hash` to its own file in `dir`.
+///
+/// Other compiler processes may be writing the same file, so the data is written to a temporary
+/// file that then replaces it, so readers never see a partially-written file.
+fn write_data_file(dir: &Path, hash: &str, data: &str, compress: bool) -> crate::Result<()> {
+    use std::io::ErrorKind;
+
+    let json_path = dir.join(format!("query-{hash}.json"));
+    let gz_path = dir.join(format!("query-{hash}.json.gz"));
+
+    let (path, other_path) = if compress {
+        (gz_path, json_path)
+    } else {
+        (json_path, gz_path)
+    };
+
+    // Delete the file in the other format so it doesn't shadow, or linger next to, this one.
+    match fs::remove_file(&other_path) {
+        Ok(()) => {}
+        Err(err)
+            if matches!(
+                err.kind(),
+                ErrorKind::NotFound | ErrorKind::PermissionDenied
+            ) => {}
+        Err(err) => return Err(format!("failed to delete {other_path:?}: {err:?}").into()),
+    }
+
+    let mut file = tempfile::NamedTempFile::new_in(dir)
+        .map_err(|err| format!("failed to create temporary file in {dir:?}: {err:?}"))?;
+
+    if compress {
+        let mut encoder = GzEncoder::new(&mut file, Compression::default());
+        write_json(&mut encoder, data)?;
+        encoder
+            .finish()
+            .map_err(|err| format!("failed to finish compressing query data: {err:?}"))?;
+    } else {
+        write_json(&mut file, data)?;
+    }
+
+    match file.persist(&path) {
+        Ok(_) => Ok(()),
+        // We overlapped with a concurrent invocation writing the same data, e.g. on Windows,
+        // where a file can't be replaced while another process has it open.
+        Err(_) if path.exists() => Ok(()),
+        Err(err) => Err(format!("failed to replace {path:?}: {err:?}").into()),
+    }
+}
+
+/// The name of the file holding the data for every query with `macros.offline_single_file`.
+const MERGED_FILENAME: &str = "queries.json";
+
+/// How long to wait for another build to finish writing `queries.json`.
+const MERGED_LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Add the data for `hash` to `queries.json` in `dir`.
+///
+/// Other compiler processes may be writing to the same file, so the read-modify-write is
+/// done holding a lock file, and the new contents are written to a temporary file that then
+/// replaces `queries.json`, so readers never see a partially-written file.
+fn write_merged_file(dir: &Path, hash: &str, data: &str) -> crate::Result<()> {
+    let _lock = LockFile::acquire(dir.join(format!("{MERGED_FILENAME}.lock")))?;
+
+    let path = dir.join(MERGED_FILENAME);
+
+    // Sorted by hash, so the file doesn't depend on the order the queries were compiled in.
+    let mut queries: BTreeMap<String, serde_json::Value> = match fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map_err(|err| format!("failed to parse {path:?}: {err}"))?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+        Err(err) => return Err(format!("failed to read {path:?}: {err:?}").into()),
+    };
+
+    queries.insert(hash.to_owned(), serde_json::from_str(data)?);
+
+    let contents = serde_json::to_string_pretty(&queries)
+        .map_err(|err| format!("failed to serialize query data: {err:?}"))?;
+
+    let mut file = tempfile::NamedTempFile::new_in(dir)
+        .map_err(|err| format!("failed to create temporary file in {dir:?}: {err:?}"))?;
+
+    write_json(&mut file, &contents)?;
+
+    file.persist(&path)
+        .map_err(|err| format!("failed to replace {path:?}: {err:?}"))?;
+
+    Ok(())
+}
+
+/// A lock file, created exclusively and deleted on drop.
+struct LockFile(PathBuf);
+
+impl LockFile {
+    fn acquire(path: PathBuf) -> crate::Result<Self> {
+        let start = Instant::now();
+
+        loop {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(_) => return Ok(Self(path)),
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                    if start.elapsed() > MERGED_LOCK_TIMEOUT {
+                        return Err(format!(
+                            "timed out waiting for {path:?}; \
+                             if no other build is running, the file is stale and may be deleted"
+                        )
+                        .into());
+                    }
+
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+                Err(err) => {
+                    return Err(format!("failed to create lock file {path:?}: {err:?}").into())
+                }
+            }
+        }
+    }
+}
+
+impl Drop for LockFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+fn write_json(out: &mut impl io::Write, data: &str) -> crate::Result<()> {
+    out.write_all(data.as_bytes())
+        .map_err(|err| format!("failed to write query data to file: {err:?}"))?;
+
+    // Ensure there is a newline at the end of the JSON file to avoid
+    // accidental modification by IDE and make github diff tool happier.
+    out.write_all(b"\n")
+        .map_err(|err| format!("failed to append a newline to file: {err:?}"))?;
+
+    Ok(())
+}
+
+/// A backend keeping query data in memory, e.g. for tests.
+#[derive(Debug, Default)]
+pub struct MemoryQueryCache {
+    entries: Mutex<HashMap<String, String>>,
+}
+
+impl QueryCache for MemoryQueryCache {
+    fn load(&self, hash: &str, sql: &str) -> crate::Result<Option<DynQueryData>> {
+        let entries = self
+            .entries
+            .lock()
+            .expect("previous panic in MemoryQueryCache");
+
+        let Some(data) = entries.get(hash) else {
+            return Ok(None);
+        };
+
+        let data: DynQueryData = serde_json::from_str(data)?;
+
+        if data.query != sql {
+            return Err("hash collision for saved query data".into());
+        }
+
+        Ok(Some(data))
+    }
+
+    fn store(&self, hash: &str, data: &str) -> crate::Result<()> {
+        self.entries
+            .lock()
+            .expect("previous panic in MemoryQueryCache")
+            .insert(hash.to_owned(), data.to_owned());
+
+        Ok(())
+    }
+}
+
+/// The hash of `sql` and the contents of a query data file for it.
+#[cfg(test)]
+fn test_query_data(sql: &str) -> (String, String) {
+    let hash = crate::query::data::hash_string(sql);
+
+    let data = serde_json::json!({
+        "db_name": "PostgreSQL",
+        "query": sql,
+        "describe": { "columns": [], "parameters": { "Left": [] }, "nullable": [] },
+        "hash": hash,
+    })
+    .to_string();
+
+    (hash, data)
+}
+
+#[test]
+fn test_memory_query_cache_round_trip() {
+    let cache = MemoryQueryCache::default();
+
+    let sql = "SELECT 1";
+    let (hash, data) = test_query_data(sql);
+
+    assert!(cache.load(&hash, sql).unwrap().is_none());
+
+    cache.store(&hash, &data).unwrap();
+
+    let loaded = cache.load(&hash, sql).unwrap().unwrap();
+    assert_eq!(loaded.db_name, "PostgreSQL");
+    assert_eq!(loaded.sql(), sql);
+    assert_eq!(loaded.db_name(), "PostgreSQL");
+    assert_eq!(loaded.hash, hash);
+    assert_eq!(
+        loaded.describe,
+        serde_json::from_str::<serde_json::Value>(&data).unwrap()["describe"]
+    );
+
+    assert!(cache.load(&hash, "SELECT 2").is_err());
+}
+
+#[test]
+fn test_write_data_file_compressed() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let sql = "SELECT 1";
+    let (hash, data) = test_query_data(sql);
+
+    let json_path = dir.path().join(format!("query-{hash}.json"));
+    let gz_path = dir.path().join(format!("query-{hash}.json.gz"));
+
+    write_data_file(dir.path(), &hash, &data, false).unwrap();
+    assert!(json_path.exists());
+
+    write_data_file(dir.path(), &hash, &data, true).unwrap();
+    assert!(!json_path.exists());
+
+    let loaded = DynQueryData::from_data_file(&gz_path, sql).unwrap();
+    assert_eq!(loaded.sql(), sql);
+    assert_eq!(loaded.db_name(), "PostgreSQL");
+    assert_eq!(loaded.hash, hash);
+
+    let Err(err) = DynQueryData::from_data_file(&gz_path, "SELECT 2") else {
+        panic!("expected an error for a different query");
+    };
+    let err = err.to_string();
+    assert!(err.contains("re-run `cargo sqlx prepare`"), "{err}");
+}
+
+#[test]
+fn test_write_data_file_concurrent() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let sql = "SELECT 1";
+    let (hash, data) = test_query_data(sql);
+
+    let path = dir.path().join(format!("query-{hash}.json"));
+
+    write_data_file(dir.path(), &hash, &data, false).unwrap();
+
+    std::thread::scope(|scope| {
+        for _ in 0..2 {
+            scope.spawn(|| {
+                for _ in 0..50 {
+                    write_data_file(dir.path(), &hash, &data, false).unwrap();
+                }
+            });
+        }
+
+        // The file is always complete, never empty or truncated.
+        scope.spawn(|| {
+            for _ in 0..100 {
+                let contents = fs::read_to_string(&path).unwrap();
+                assert_eq!(contents, format!("{data}\n"));
+            }
+        });
+    });
+
+    // No temporary files are left behind.
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_data_file_whitespace_mismatch() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let queries = ["SELECT 1 -- one\n, 2", "SELECT 1 -- one , 2"];
+
+    let paths = queries.map(|sql| {
+        let (hash, data) = test_query_data(sql);
+
+        write_data_file(dir.path(), &hash, &data, false).unwrap();
+        dir.path().join(format!("query-{hash}.json"))
+    });
+
+    for (i, path) in paths.iter().enumerate() {
+        assert!(DynQueryData::from_data_file(path, queries[i]).is_ok());
+
+        let Err(err) = DynQueryData::from_data_file(path, queries[1 - i]) else {
+            panic!("query {i}'s data accepted for the other query");
+        };
+        let err = err.to_string();
+        assert!(err.contains("re-run `cargo sqlx prepare`"), "{err}");
+    }
+}
+
+#[test]
+fn test_write_merged_file() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let (hash_1, data_1) = test_query_data("SELECT 1");
+    let (hash_2, data_2) = test_query_data("SELECT 2");
+
+    write_merged_file(dir.path(), &hash_1, &data_1).unwrap();
+    write_merged_file(dir.path(), &hash_2, &data_2).unwrap();
+
+    assert!(!dir.path().join("queries.json.lock").exists());
+
+    let path = dir.path().join(MERGED_FILENAME);
+
+    let loaded = DynQueryData::from_merged_file(&path, &hash_2, "SELECT 2")
+        .unwrap()
+        .unwrap();
+    assert_eq!(loaded.hash, hash_2);
+
+    assert!(DynQueryData::from_merged_file(&path, &hash_1, "SELECT 1")
+        .unwrap()
+        .is_some());
+    assert!(DynQueryData::from_merged_file(&path, "0000", "SELECT 3")
+        .unwrap()
+        .is_none());
+    assert!(DynQueryData::from_merged_file(&path, &hash_1, "SELECT 3").is_err());
+}
+
+#[test]
+fn test_load_from_dirs() {
+    let first = tempfile::tempdir().unwrap();
+    let second = tempfile::tempdir().unwrap();
+
+    let sql = "SELECT 1";
+    let (hash, data) = test_query_data(sql);
+
+    write_data_file(second.path(), &hash, &data, false).unwrap();
+
+    let dirs = std::env::split_paths(&std::env::join_paths([first.path(), second.path()]).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(dirs.len(), 2);
+
+    let loaded = load_from_dirs(dirs.clone().into_iter(), &hash, sql)
+        .unwrap()
+        .unwrap();
+    assert_eq!(loaded.hash, hash);
+
+    assert!(load_from_dirs(dirs.into_iter().take(1), &hash, sql)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_load_for_file_checks_query_file_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let sqlx_dir = dir.path().join(".sqlx");
+    fs::create_dir(&sqlx_dir).unwrap();
+
+    let sql = "SELECT 'test_load_for_file_checks_query_file_dir'";
+    let (hash, data) = test_query_data(sql);
+
+    write_data_file(&sqlx_dir, &hash, &data, false).unwrap();
+
+    let file_path = dir.path().join("query.sql");
+
+    let loaded = FilesystemQueryCache
+        .load_for_file(&hash, sql, &file_path)
+        .unwrap()
+        .unwrap();
+    assert_eq!(loaded.sql(), sql);
+
+    // only queries from that file look there
+    assert!(FilesystemQueryCache.load(&hash, sql).unwrap().is_none());
+}
@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
 use std::fs;
-use std::io::Write as _;
+use std::io::{self, Read as _};
 use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, PoisonError};
 
+use flate2::read::GzDecoder;
 use once_cell::sync::Lazy;
 use serde::{Serialize, Serializer};
 
@@ -19,7 +20,6 @@ use crate::database::DatabaseExt;
 #[derive(Debug)]
 pub struct QueryData<DB: Database> {
     db_name: SerializeDbName<DB>,
-    #[allow(dead_code)]
     pub(super) query: String,
     pub(super) describe: Describe<DB>,
     pub(super) hash: String,
@@ -68,6 +68,13 @@ impl<DB: Database> Serialize for SerializeDbName<DB> {
 static OFFLINE_DATA_CACHE: Lazy<Mutex<HashMap<PathBuf, DynQueryData>>> =
     Lazy::new(Default::default);
 
+/// The entries of a `queries.json` file, keyed by hash.
+type MergedQueryData = Arc<HashMap<String, DynQueryData>>;
+
+/// The parsed `queries.json` files, keyed by path.
+static MERGED_DATA_CACHE: Lazy<Mutex<HashMap<PathBuf, MergedQueryData>>> =
+    Lazy::new(Default::default);
+
 /// Offline query data
 #[derive(Clone, serde::Deserialize)]
 pub struct DynQueryData {
@@ -78,8 +85,19 @@ pub struct DynQueryData {
 }
 
 impl DynQueryData {
-    /// Loads a query given the path to its "query-<hash>.json" file. Subsequent calls for the same
-    /// path are retrieved from an in-memory cache.
+    /// The SQL of the query this data describes.
+    pub fn sql(&self) -> &str {
+        &self.query
+    }
+
+    /// The database the query was described against, as named by `Database::NAME`,
+    /// e.g. `"PostgreSQL"`.
+    pub fn db_name(&self) -> &str {
+        &self.db_name
+    }
+
+    /// Loads a query given the path to its "query-<hash>.json" or gzipped "query-<hash>.json.gz"
+    /// file. Subsequent calls for the same path are retrieved from an in-memory cache.
     pub fn from_data_file(path: impl AsRef<Path>, query: &str) -> crate::Result<Self> {
         let path = path.as_ref();
 
@@ -92,9 +110,7 @@ impl DynQueryData {
                 guard
             });
         if let Some(cached) = cache.get(path).cloned() {
-            if query != cached.query {
-                return Err("hash collision for saved query data".into());
-            }
+            check_query(path, &cached.db_name, &cached.query, query)?;
             return Ok(cached);
         }
 
@@ -111,17 +127,90 @@ impl DynQueryData {
             proc_macro::tracked_path::path(path);
         }
 
-        let offline_data_contents = fs::read_to_string(path)
+        let offline_data_contents = read_data_file(path)
             .map_err(|e| format!("failed to read saved query path {}: {}", path.display(), e))?;
         let dyn_data: DynQueryData = serde_json::from_str(&offline_data_contents)?;
 
-        if query != dyn_data.query {
-            return Err("hash collision for saved query data".into());
-        }
+        check_query(path, &dyn_data.db_name, &dyn_data.query, query)?;
 
         let _ = cache.insert(path.to_owned(), dyn_data.clone());
         Ok(dyn_data)
     }
+
+    /// Loads the query with the given hash from a merged "queries.json" file, if it has an entry
+    /// for it. The file is only read and parsed once.
+    pub fn from_merged_file(
+        path: impl AsRef<Path>,
+        hash: &str,
+        query: &str,
+    ) -> crate::Result<Option<Self>> {
+        let path = path.as_ref();
+
+        let queries = {
+            let mut cache = MERGED_DATA_CACHE
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+
+            match cache.get(path) {
+                Some(queries) => queries.clone(),
+                None => {
+                    let contents = fs::read_to_string(path).map_err(|e| {
+                        format!("failed to read saved query path {}: {}", path.display(), e)
+                    })?;
+
+                    let queries: MergedQueryData =
+                        Arc::new(serde_json::from_str(&contents).map_err(|e| {
+                            format!("failed to parse saved query path {}: {}", path.display(), e)
+                        })?);
+
+                    cache.insert(path.to_owned(), queries.clone());
+                    queries
+                }
+            }
+        };
+
+        let Some(dyn_data) = queries.get(hash) else {
+            return Ok(None);
+        };
+
+        check_query(path, &dyn_data.db_name, &dyn_data.query, query)?;
+
+        Ok(Some(dyn_data.clone()))
+    }
+}
+
+/// Check that the query data loaded from `path` is for `query`.
+///
+/// The data is found by the hash of the query, so this only fails on a hash collision, if
+/// the hash changed between SQLx versions or if the file was edited. The query must match
+/// after [`normalize_sql()`], same as the hash; queries differing only in whitespace may still
+/// differ after normalizing, e.g. if one has a comment cut short by a line break.
+///
+/// `#` only starts a comment in MySQL, so for other databases the text after it has to match too.
+fn check_query(path: &Path, db_name: &str, cached: &str, query: &str) -> crate::Result<()> {
+    let hash_comments = db_name == "MySQL";
+
+    if normalize(cached, hash_comments) != normalize(query, hash_comments) {
+        return Err(format!(
+            "cached query data in {} is for a different query; \
+             it is stale or was edited, re-run `cargo sqlx prepare`",
+            path.display()
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Read a data file, decompressing it if it's a `.gz` file.
+fn read_data_file(path: &Path) -> io::Result<String> {
+    if path.extension().map_or(false, |ext| ext == "gz") {
+        let mut contents = String::new();
+        GzDecoder::new(fs::File::open(path)?).read_to_string(&mut contents)?;
+        Ok(contents)
+    } else {
+        fs::read_to_string(path)
+    }
 }
 
 impl<DB: DatabaseExt> QueryData<DB>
@@ -150,49 +239,188 @@ where
         }
     }
 
-    pub(super) fn save_in(&self, dir: impl AsRef<Path>) -> crate::Result<()> {
-        use std::io::ErrorKind;
+    /// Convert to the database-agnostic form, as if read back from a `query-<hash>.json` file.
+    pub fn into_dyn(self) -> crate::Result<DynQueryData> {
+        Ok(DynQueryData {
+            db_name: DB::NAME.into(),
+            describe: serialize_describe(&self.describe)?,
+            query: self.query,
+            hash: self.hash,
+        })
+    }
 
-        let path = dir.as_ref().join(format!("query-{}.json", self.hash));
-        match std::fs::remove_file(&path) {
-            Ok(()) => {}
-            Err(err)
-                if matches!(
-                    err.kind(),
-                    ErrorKind::NotFound | ErrorKind::PermissionDenied,
-                ) => {}
-            Err(err) => return Err(format!("failed to delete {path:?}: {err:?}").into()),
-        }
-        let mut file = match std::fs::OpenOptions::new()
-            .write(true)
-            .create_new(true)
-            .open(&path)
-        {
-            Ok(file) => file,
-            // We overlapped with a concurrent invocation and the other one succeeded.
-            Err(err) if matches!(err.kind(), ErrorKind::AlreadyExists) => return Ok(()),
-            Err(err) => {
-                return Err(format!("failed to exclusively create {path:?}: {err:?}").into())
+    /// Like [`Self::into_dyn()`], but clones the query and hash.
+    pub fn to_dyn(&self) -> crate::Result<DynQueryData> {
+        Ok(DynQueryData {
+            db_name: DB::NAME.into(),
+            query: self.query.clone(),
+            describe: serialize_describe(&self.describe)?,
+            hash: self.hash.clone(),
+        })
+    }
+
+    /// Serialize to the JSON format of `query-<hash>.json` files.
+    pub(super) fn to_json(&self) -> crate::Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|err| format!("failed to serialize query data: {err:?}").into())
+    }
+}
+
+fn serialize_describe<DB: Database>(describe: &Describe<DB>) -> crate::Result<serde_json::Value>
+where
+    Describe<DB>: serde::Serialize,
+{
+    serde_json::to_value(describe)
+        .map_err(|err| format!("failed to serialize query data: {err:?}").into())
+}
+
+/// The hash naming the cached data for `query`, as in `query-<hash>.json`.
+///
+/// The query is hashed after [`normalize_sql()`], so reformatting it or editing its comments
+/// doesn't invalidate its cached data.
+pub fn hash_string(query: &str) -> String {
+    // picked `sha2` because it's already in the dependency tree for both MySQL and Postgres
+    use sha2::{Digest, Sha256};
+
+    hex::encode(Sha256::digest(normalize_sql(query).as_bytes()))
+}
+
+/// Strip the comments from `sql` and collapse each run of whitespace to a single space.
+///
+/// Comments are `-- ...` and `# ...` to the end of the line, and `/* ... */`. `#` is an operator
+/// rather than a comment in Postgres, but the hash has to be the same before the database is
+/// known; loading cached data compares the text after it for databases other than MySQL.
+///
+/// String literals, quoted identifiers and Postgres dollar-quoted strings are kept as-is.
+pub fn normalize_sql(sql: &str) -> String {
+    normalize(sql, true)
+}
+
+fn normalize(sql: &str, hash_comments: bool) -> String {
+    let mut normalized = String::with_capacity(sql.len());
+    let mut space = false;
+    let mut rest = sql;
+
+    while let Some(c) = rest.chars().next() {
+        let len = if c.is_whitespace() {
+            space = true;
+            c.len_utf8()
+        } else if rest.starts_with("--") || (hash_comments && rest.starts_with('#')) {
+            space = true;
+            rest.find('\n').unwrap_or(rest.len())
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            space = true;
+            comment.find("*/").map_or(rest.len(), |end| end + 4)
+        } else {
+            if space && !normalized.is_empty() {
+                normalized.push(' ');
             }
+            space = false;
+
+            let len = quoted_len(rest).unwrap_or(c.len_utf8());
+            normalized.push_str(&rest[..len]);
+            len
         };
 
-        let data = serde_json::to_string_pretty(self)
-            .map_err(|err| format!("failed to serialize query data: {err:?}"))?;
-        file.write_all(data.as_bytes())
-            .map_err(|err| format!("failed to write query data to file: {err:?}"))?;
+        rest = &rest[len..];
+    }
+
+    normalized
+}
 
-        // Ensure there is a newline at the end of the JSON file to avoid
-        // accidental modification by IDE and make github diff tool happier.
-        file.write_all(b"\n")
-            .map_err(|err| format!("failed to append a newline to file: {err:?}"))?;
+/// The length of the quoted string or identifier at the start of `sql`, including the quotes.
+fn quoted_len(sql: &str) -> Option<usize> {
+    let quote = match sql.chars().next()? {
+        quote @ ('\'' | '"' | '`') => quote.to_string(),
+        '$' => {
+            // `$tag$` or `$$`, but not a `$1` placeholder
+            let tag_len = sql[1..].find(|c: char| !(c.is_alphanumeric() || c == '_'))?;
+            let tag = &sql[..tag_len + 2];
 
-        Ok(())
-    }
+            if !tag.ends_with('$') || tag[1..].starts_with(|c: char| c.is_ascii_digit()) {
+                return None;
+            }
+
+            tag.to_string()
+        }
+        _ => return None,
+    };
+
+    // a doubled quote inside the string is just two strings in a row here, which is the same
+    let end = sql[quote.len()..]
+        .find(&quote)
+        .map_or(sql.len(), |end| end + 2 * quote.len());
+
+    Some(end)
 }
 
-pub(super) fn hash_string(query: &str) -> String {
-    // picked `sha2` because it's already in the dependency tree for both MySQL and Postgres
-    use sha2::{Digest, Sha256};
+#[test]
+#[cfg(feature = "postgres")]
+fn test_query_data_into_dyn() {
+    use sqlx_postgres::Postgres;
+
+    let describe: Describe<Postgres> = serde_json::from_value(serde_json::json!({
+        "columns": [],
+        "parameters": { "Left": [] },
+        "nullable": [],
+    }))
+    .unwrap();
+
+    let data = QueryData::from_describe("SELECT 1", describe);
+
+    let dyn_data = data.to_dyn().unwrap();
+    assert_eq!(dyn_data.db_name(), "PostgreSQL");
+    assert_eq!(dyn_data.sql(), "SELECT 1");
+    assert_eq!(dyn_data.hash, data.hash);
+
+    let data = QueryData::<Postgres>::from_dyn_data(data.into_dyn().unwrap()).unwrap();
+    assert_eq!(data.query, "SELECT 1");
+    assert_eq!(data.hash, hash_string("SELECT 1"));
+}
+
+#[test]
+fn test_normalize_sql() {
+    assert_eq!(
+        normalize_sql("  SELECT id,\n       name -- the name\n  FROM users /* all of them */\n"),
+        "SELECT id, name FROM users"
+    );
+
+    assert_eq!(
+        hash_string("SELECT id,\n  name\nFROM users"),
+        hash_string("SELECT id, name /* comment */ FROM users -- comment")
+    );
+
+    // quotes are kept verbatim, including comment markers and runs of whitespace
+    let quoted =
+        r#"SELECT 'a  -- b', "c  /* d */", 'it''s  here', `e  f`, $x$ g  h $x$, $$ i  j $$"#;
+    assert_eq!(normalize_sql(quoted), quoted);
+    assert_ne!(hash_string("SELECT 'a  b'"), hash_string("SELECT 'a b'"));
+
+    // placeholders aren't dollar quotes
+    assert_eq!(normalize_sql("SELECT $1,  $2"), "SELECT $1, $2");
+
+    // a comment cut short by a line break leaves the rest of the query
+    assert_ne!(
+        normalize_sql("SELECT 1 -- one\n, 2"),
+        normalize_sql("SELECT 1 -- one , 2")
+    );
+
+    // MySQL comments
+    assert_eq!(normalize_sql("SELECT 1 # one\n, 2"), "SELECT 1 , 2");
+    assert_eq!(normalize_sql("SELECT '#', `#`"), "SELECT '#', `#`");
+}
+
+#[test]
+fn test_check_query_hash_comments() {
+    let path = Path::new("query-0000.json");
+
+    let cached = "SELECT 1 # one\nFROM t";
+    let query = "SELECT 1 # uno\nFROM t";
+    assert_eq!(hash_string(cached), hash_string(query));
+
+    assert!(check_query(path, "MySQL", cached, query).is_ok());
 
-    hex::encode(Sha256::digest(query.as_bytes()))
+    // `#` is XOR in Postgres
+    assert!(check_query(path, "PostgreSQL", cached, query).is_err());
+    assert!(check_query(path, "PostgreSQL", cached, cached).is_ok());
 }
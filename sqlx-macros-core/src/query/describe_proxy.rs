@@ -0,0 +1,125 @@
+use std::io::{self, Read};
+use std::net::{Ipv4Addr, SocketAddr, TcpStream};
+use std::process::{Child, Command, Stdio};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use sqlx_core::config::macros::DescribeProxyCommand;
+use url::Url;
+
+const DEFAULT_READY_TIMEOUT: Duration = Duration::from_secs(10);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The proxy process is spawned on first use and shared by all queries in this process.
+///
+/// If it failed to come up, the error is kept so we don't try (and time out) again for every query.
+static PROXY: Mutex<Option<Result<Child, String>>> = Mutex::new(None);
+
+/// Ensure the proxy command is running and return `database_url` rewritten to connect through it.
+pub fn proxied_url(proxy: &DescribeProxyCommand, database_url: &str) -> crate::Result<String> {
+    let mut url: Url = database_url.parse()?;
+
+    let mut state = PROXY
+        .lock()
+        // The `Child` is still valid if another query panicked while holding the lock.
+        .unwrap_or_else(|poison_err| poison_err.into_inner());
+
+    let child = state.get_or_insert_with(|| spawn(proxy));
+
+    match child {
+        Ok(running) => {
+            if let Some(status) = running.try_wait()? {
+                let message = format!("describe proxy command exited unexpectedly ({status})");
+                *child = Err(message.clone());
+                return Err(message.into());
+            }
+        }
+        Err(message) => return Err(message.clone().into()),
+    }
+
+    url.set_host(Some(&Ipv4Addr::LOCALHOST.to_string()))
+        .map_err(|e| format!("cannot use describe proxy with database URL: {e}"))?;
+    url.set_port(Some(proxy.local_port))
+        .map_err(|()| "cannot use describe proxy with a database URL that has no port")?;
+
+    Ok(url.into())
+}
+
+fn spawn(proxy: &DescribeProxyCommand) -> Result<Child, String> {
+    let (program, args) = proxy
+        .command
+        .split_first()
+        .ok_or("`macros.describe_proxy_command.command` in sqlx.toml must not be empty")?;
+
+    let mut child = Command::new(program)
+        .args(args)
+        // Holding the write end of stdin lets the command detect when the compiler exits.
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        // Inheriting stderr would keep Cargo waiting on the compiler's output until the
+        // command exits, so we capture it instead.
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to spawn describe proxy command {program:?}: {e}"))?;
+
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, proxy.local_port));
+    let timeout = proxy
+        .ready_timeout_secs
+        .map_or(DEFAULT_READY_TIMEOUT, Duration::from_secs);
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                return Err(format!(
+                    "describe proxy command {program:?} exited before port {} accepted connections ({status}){}",
+                    proxy.local_port,
+                    stderr_of(&mut child),
+                ));
+            }
+            Ok(None) => (),
+            Err(e) => return Err(format!("failed to check on describe proxy command: {e}")),
+        }
+
+        if TcpStream::connect_timeout(&addr, POLL_INTERVAL).is_ok() {
+            // Keep draining stderr so the command never blocks on a full pipe.
+            if let Some(mut stderr) = child.stderr.take() {
+                thread::spawn(move || io::copy(&mut stderr, &mut io::sink()));
+            }
+
+            return Ok(child);
+        }
+
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+
+            return Err(format!(
+                "describe proxy command {program:?} did not accept connections on port {} within {timeout:?}{}",
+                proxy.local_port,
+                stderr_of(&mut child),
+            ));
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Format the output of an exited command for inclusion in an error message.
+fn stderr_of(child: &mut Child) -> String {
+    let mut output = String::new();
+
+    if let Some(mut stderr) = child.stderr.take() {
+        let _ = stderr.read_to_string(&mut output);
+    }
+
+    let output = output.trim();
+
+    if output.is_empty() {
+        String::new()
+    } else {
+        format!("; stderr:\n{output}")
+    }
+}
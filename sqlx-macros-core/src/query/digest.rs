@@ -0,0 +1,72 @@
+//! A combined digest of every query expanded in a crate, see `macros.query_digest_path`.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use crate::query::data::hash_string;
+
+static QUERY_HASHES: Lazy<Mutex<BTreeSet<String>>> = Lazy::new(Default::default);
+
+/// Add the hash of `sql` to the set of queries seen by this compiler process, and write the
+/// digest of the set to `path`.
+///
+/// There's no hook for the end of macro expansion, so the file is rewritten whenever a new query
+/// is added; once the crate has finished compiling it holds the digest of all of its queries.
+pub fn record(path: &Path, sql: &str) -> crate::Result<()> {
+    let mut hashes = QUERY_HASHES
+        .lock()
+        // Just reset the set on error
+        .unwrap_or_else(|poison_err| {
+            let mut guard = poison_err.into_inner();
+            *guard = Default::default();
+            guard
+        });
+
+    if !hashes.insert(hash_string(sql)) {
+        return Ok(());
+    }
+
+    let digest = combined_digest(hashes.iter().map(String::as_str));
+
+    fs::write(path, format!("{digest}\n"))
+        .map_err(|e| format!("failed to write query digest to {}: {e}", path.display()).into())
+}
+
+/// Hash a set of query hashes, independent of the order in which they are given.
+fn combined_digest<'a>(hashes: impl IntoIterator<Item = &'a str>) -> String {
+    let sorted: BTreeSet<&str> = hashes.into_iter().collect();
+
+    hash_string(&sorted.into_iter().collect::<Vec<_>>().join("\n"))
+}
+
+#[test]
+fn test_combined_digest() {
+    let a = hash_string("SELECT 1");
+    let b = hash_string("SELECT 2");
+    let c = hash_string("SELECT 3");
+
+    // the same queries in a different expansion order give the same digest
+    assert_eq!(
+        combined_digest([a.as_str(), b.as_str(), c.as_str()]),
+        combined_digest([c.as_str(), a.as_str(), b.as_str()])
+    );
+
+    // expanding a query more than once doesn't change the digest
+    assert_eq!(
+        combined_digest([a.as_str(), b.as_str()]),
+        combined_digest([b.as_str(), a.as_str(), b.as_str()])
+    );
+
+    assert_ne!(
+        combined_digest([a.as_str(), b.as_str()]),
+        combined_digest([a.as_str(), c.as_str()])
+    );
+    assert_ne!(
+        combined_digest([a.as_str()]),
+        combined_digest([a.as_str(), b.as_str()])
+    );
+}
@@ -1,11 +1,16 @@
 use std::fs;
+use std::time::Duration;
 
 use proc_macro2::{Ident, Span};
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{Expr, LitBool, LitStr, Token};
+use syn::spanned::Spanned;
+use syn::{Expr, LitBool, LitInt, LitStr, Token};
 use syn::{ExprArray, Type};
 
+use crate::query::output::NullabilityHint;
+use crate::query::placeholders;
+
 /// Macro input shared by `query!()` and `query_file!()`
 pub struct QueryMacroInput {
     pub(super) sql: String,
@@ -16,9 +21,38 @@ pub struct QueryMacroInput {
 
     pub(super) arg_exprs: Vec<Expr>,
 
+    /// The value passed as `params = ..`, if any, whose fields are bound to the `$name`
+    /// placeholders of the same names.
+    ///
+    /// [`Self::number_named_params()`] fills in `arg_exprs` with the field accesses.
+    pub(super) params: Option<Expr>,
+
     pub(super) checked: bool,
 
     pub(super) file_path: Option<String>,
+
+    /// Whether `sql` differs from the contents of the file at `file_path`, e.g. because hint
+    /// comments were stripped, so the query can't be taken from `include_str!()`.
+    pub(super) rewritten: bool,
+
+    pub(super) timeout: Option<(Duration, Span)>,
+
+    /// The number of leading columns to map to the fields of the given record type,
+    /// ignoring the rest, from a `#[columns = N]` hint.
+    pub(super) take_columns: Option<(usize, Span)>,
+
+    /// Whether the fields of the given record type that aren't columns of the query are filled
+    /// in from its `Default` impl, from a `#[default]` hint.
+    pub(super) default_fields: Option<Span>,
+
+    /// The URL scheme of the driver to expand with, from a `#[driver = "..."]` hint.
+    pub(super) driver: Option<LitStr>,
+
+    /// The name of the query's `[macros.queries]` entry, from a `#[name = "..."]` hint.
+    pub(super) name: Option<LitStr>,
+
+    /// The nullability overrides from `/* sqlx:... */` comments, which are stripped from `sql`.
+    pub(super) nullability_hint: NullabilityHint,
 }
 
 enum QuerySrc {
@@ -29,6 +63,8 @@ enum QuerySrc {
 pub enum RecordType {
     Given(Type),
     Scalar,
+    /// A tuple of the columns' types, in order.
+    Tuple,
     Generated,
 }
 
@@ -38,6 +74,11 @@ impl Parse for QueryMacroInput {
         let mut args: Option<Vec<Expr>> = None;
         let mut record_type = RecordType::Generated;
         let mut checked = true;
+        let mut timeout = None;
+        let mut take_columns = None;
+        let mut default_fields = None;
+        let mut driver = None;
+        let mut name = None;
 
         let mut expect_comma = false;
 
@@ -65,13 +106,13 @@ impl Parse for QueryMacroInput {
                 args = Some(exprs.elems.into_iter().collect())
             } else if key == "record" {
                 if !matches!(record_type, RecordType::Generated) {
-                    return Err(input.error("colliding `scalar` or `record` key"));
+                    return Err(input.error("colliding `scalar`, `tuple` or `record` key"));
                 }
 
                 record_type = RecordType::Given(input.parse()?);
             } else if key == "scalar" {
                 if !matches!(record_type, RecordType::Generated) {
-                    return Err(input.error("colliding `scalar` or `record` key"));
+                    return Err(input.error("colliding `scalar`, `tuple` or `record` key"));
                 }
 
                 // we currently expect only `scalar = _`
@@ -79,9 +120,32 @@ impl Parse for QueryMacroInput {
                 // of the column in SQL
                 input.parse::<syn::Token![_]>()?;
                 record_type = RecordType::Scalar;
+            } else if key == "tuple" {
+                if !matches!(record_type, RecordType::Generated) {
+                    return Err(input.error("colliding `scalar`, `tuple` or `record` key"));
+                }
+
+                // like `scalar = _`, the type comes from the columns
+                input.parse::<syn::Token![_]>()?;
+                record_type = RecordType::Tuple;
             } else if key == "checked" {
                 let lit_bool = input.parse::<LitBool>()?;
                 checked = lit_bool.value;
+            } else if key == "timeout" {
+                let lit_str = input.parse::<LitStr>()?;
+                let duration = parse_duration(&lit_str.value())
+                    .map_err(|e| syn::Error::new_spanned(&lit_str, e))?;
+                timeout = Some((duration, lit_str.span()));
+            } else if key == "columns" {
+                let lit_int = input.parse::<LitInt>()?;
+                take_columns = Some((lit_int.base10_parse()?, lit_int.span()));
+            } else if key == "default" {
+                let lit_bool = input.parse::<LitBool>()?;
+                default_fields = lit_bool.value.then_some(lit_bool.span);
+            } else if key == "driver" {
+                driver = Some(input.parse::<LitStr>()?);
+            } else if key == "name" {
+                name = Some(input.parse::<LitStr>()?);
             } else {
                 let message = format!("unexpected input key: {key}");
                 return Err(syn::Error::new_spanned(key, message));
@@ -93,21 +157,146 @@ impl Parse for QueryMacroInput {
         let (src, src_span) =
             query_src.ok_or_else(|| input.error("expected `source` or `source_file` key"))?;
 
-        let arg_exprs = args.unwrap_or_default();
+        if let Some((_, span)) = take_columns {
+            if !matches!(record_type, RecordType::Given(_)) {
+                return Err(syn::Error::new(
+                    span,
+                    "`#[columns = N]` is only supported with an explicit record type, \
+                     e.g. `query_as!()` and its variants",
+                ));
+            }
+        }
+
+        if let Some(span) = default_fields {
+            if !matches!(record_type, RecordType::Given(_)) {
+                return Err(syn::Error::new(
+                    span,
+                    "`#[default]` is only supported with an explicit record type, \
+                     e.g. `query_as!()` and its variants",
+                ));
+            }
+        }
+
+        let (arg_exprs, params) = split_params(args.unwrap_or_default());
 
         let file_path = src.file_path(src_span)?;
 
+        let sql = src.resolve(src_span)?;
+
+        let (nullability_hint, stripped) =
+            NullabilityHint::strip(&sql).map_err(|e| syn::Error::new(src_span, e))?;
+
+        // the hints must not be sent to the database with the query
+        let rewritten = stripped.len() != sql.len();
+
         Ok(QueryMacroInput {
-            sql: src.resolve(src_span)?,
+            sql: stripped.to_string(),
             src_span,
             record_type,
             arg_exprs,
+            params,
             checked,
             file_path,
+            rewritten,
+            timeout,
+            take_columns,
+            default_fields,
+            driver,
+            name,
+            nullability_hint,
         })
     }
 }
 
+impl QueryMacroInput {
+    /// Number the `$name` placeholders in the query, for `macros.enable_named_params`, and
+    /// reorder the arguments to match.
+    ///
+    /// Each name must have an argument that is the variable of the same name, and each
+    /// argument must be used by a placeholder. With `params = ..`, each name is bound to the
+    /// field of that name instead.
+    pub(super) fn number_named_params(&mut self) -> syn::Result<()> {
+        let (sql, names) = placeholders::number_named_placeholders(&self.sql)
+            .map_err(|e| syn::Error::new(self.src_span, e))?;
+
+        if let Some(params) = &self.params {
+            if names.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    params,
+                    "`params = ..` binds the fields named by `$name` placeholders, \
+                     but the query has none",
+                ));
+            }
+
+            self.arg_exprs = names
+                .iter()
+                .map(|name| {
+                    let field = syn::parse_str::<Ident>(name).map_err(|_| {
+                        syn::Error::new_spanned(
+                            params,
+                            format!("`${name}` can't be bound to a field of `params`"),
+                        )
+                    })?;
+
+                    // `params` is bound to the value in `quote_args()`
+                    Ok(syn::parse_quote_spanned!(params.span() => params.#field))
+                })
+                .collect::<syn::Result<_>>()?;
+
+            self.sql = sql;
+            self.rewritten = true;
+
+            return Ok(());
+        }
+
+        if names.is_empty() {
+            return Ok(());
+        }
+
+        let mut args = std::mem::take(&mut self.arg_exprs)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+
+        for name in &names {
+            let Some(arg) = args.iter_mut().find(|arg| {
+                arg.as_ref()
+                    .and_then(arg_name)
+                    .map_or(false, |n| n == *name)
+            }) else {
+                return Err(syn::Error::new(
+                    self.src_span,
+                    format!("no argument for `${name}`; pass the variable `{name}` as an argument"),
+                ));
+            };
+
+            self.arg_exprs.extend(arg.take());
+        }
+
+        if let Some(arg) = args.into_iter().flatten().next() {
+            return Err(syn::Error::new_spanned(
+                arg,
+                "argument is not used by any `$name` placeholder in the query",
+            ));
+        }
+
+        self.sql = sql;
+        self.rewritten = true;
+
+        Ok(())
+    }
+}
+
+/// The name of the variable passed as `arg`, if it is one, ignoring casts.
+fn arg_name(arg: &Expr) -> Option<String> {
+    match arg {
+        Expr::Group(group) => arg_name(&group.expr),
+        Expr::Cast(cast) => arg_name(&cast.expr),
+        Expr::Path(path) => path.path.get_ident().map(ToString::to_string),
+        _ => None,
+    }
+}
+
 impl QuerySrc {
     /// If the query source is a file, read it to a string. Otherwise return the query string.
     fn resolve(self, source_span: Span) -> syn::Result<String> {
@@ -153,3 +342,60 @@ fn read_file_src(source: &str, source_span: Span) -> syn::Result<String> {
         )
     })
 }
+
+/// If the only argument is `params = <expr>`, take the expression.
+fn split_params(args: Vec<Expr>) -> (Vec<Expr>, Option<Expr>) {
+    match &args[..] {
+        [Expr::Assign(assign)] if matches!(&*assign.left, Expr::Path(path) if path.path.is_ident("params")) => {
+            (Vec::new(), Some((*assign.right).clone()))
+        }
+        _ => (args, None),
+    }
+}
+
+/// Parse a timeout hint such as `"200ms"`, `"5s"` or `"1m"`.
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let unit_start = s
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| format!("timeout {s:?} is missing a unit (expected `ms`, `s` or `m`)"))?;
+
+    let (value, unit) = s.split_at(unit_start);
+
+    let value: u64 = value
+        .parse()
+        .map_err(|_| format!("timeout {s:?} must start with a whole number"))?;
+
+    let millis_per_unit = match unit {
+        "ms" => 1,
+        "s" => 1_000,
+        "m" => 60_000,
+        _ => {
+            return Err(format!(
+                "timeout {s:?} has an unknown unit {unit:?} (expected `ms`, `s` or `m`)"
+            ))
+        }
+    };
+
+    match value.checked_mul(millis_per_unit) {
+        Some(0) => Err(format!("timeout {s:?} must be greater than zero")),
+        Some(millis) => Ok(Duration::from_millis(millis)),
+        None => Err(format!("timeout {s:?} is too large")),
+    }
+}
+
+#[test]
+fn test_parse_duration() {
+    assert_eq!(parse_duration("200ms"), Ok(Duration::from_millis(200)));
+    assert_eq!(parse_duration("5s"), Ok(Duration::from_secs(5)));
+    assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+
+    assert!(parse_duration("").is_err());
+    assert!(parse_duration("200").is_err());
+    assert!(parse_duration("ms").is_err());
+    assert!(parse_duration("0s").is_err());
+    assert!(parse_duration("-5s").is_err());
+    assert!(parse_duration("1.5s").is_err());
+    assert!(parse_duration("5 s").is_err());
+    assert!(parse_duration("5h").is_err());
+    assert!(parse_duration("18446744073709551615m").is_err());
+}
@@ -0,0 +1,262 @@
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::{Lazy, OnceCell};
+use sqlx_core::config::{Config, ConfigError};
+
+use super::env;
+
+pub struct Metadata {
+    #[allow(unused)]
+    pub manifest_dir: PathBuf,
+    pub offline: bool,
+    /// Whether cached data is checked against the database when building offline with a
+    /// database URL set, from `SQLX_OFFLINE_CHECK`.
+    pub offline_check: bool,
+    /// Whether query data described online is not saved, from `SQLX_NO_SAVE`.
+    pub no_save: bool,
+    /// Whether cached data is used if the database can't be reached, from
+    /// `SQLX_OFFLINE_FALLBACK` or `macros.offline_fallback`.
+    pub offline_fallback: bool,
+    pub database_url: Option<String>,
+    /// The `[macros]` section of `sqlx.toml`, if the file exists.
+    pub config: Option<&'static sqlx_core::config::macros::Config>,
+    workspace_root: OnceCell<Result<PathBuf, String>>,
+}
+
+impl Metadata {
+    /// Get the metadata, or the error from loading it as a message.
+    ///
+    /// Loading fails if `CARGO_MANIFEST_DIR` isn't set, or if `.env` or `sqlx.toml` exist
+    /// but could not be loaded. The macros must use this instead of [`Self::get()`] so that
+    /// a mistake in the user's files is reported as a compile error rather than a proc-macro
+    /// panic.
+    pub fn try_get() -> crate::Result<&'static Self> {
+        METADATA.as_ref().map_err(|e| e.clone().into())
+    }
+
+    /// Get the metadata, for callers that run after [`Self::try_get()`] has succeeded.
+    ///
+    /// ### Panics
+    /// If the metadata could not be loaded.
+    pub fn get() -> &'static Self {
+        match &*METADATA {
+            Ok(metadata) => metadata,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// The names of the environment variables to read the database URL from, in order.
+    pub fn url_vars(&self) -> Vec<&str> {
+        url_vars(self.config)
+    }
+
+    /// The root of the Cargo workspace the crate is in, from `cargo metadata`.
+    ///
+    /// `cargo metadata` is only run the first time this is called in the compiler process;
+    /// the result, or the error, is reused for the remaining queries.
+    pub fn workspace_root(&self) -> crate::Result<PathBuf> {
+        self.workspace_root
+            .get_or_init(|| {
+                cargo_workspace_root(&self.manifest_dir)
+                    .map_err(|e| format!("failed to find the workspace root: {e}"))
+            })
+            .clone()
+            .map_err(Into::into)
+    }
+}
+
+fn cargo_workspace_root(manifest_dir: &Path) -> Result<PathBuf, String> {
+    use serde::Deserialize;
+    use std::process::Command;
+
+    let cargo = env("CARGO").map_err(|_| "`CARGO` must be set")?;
+
+    let output = Command::new(cargo)
+        .args(["metadata", "--format-version=1", "--no-deps"])
+        .current_dir(manifest_dir)
+        .env_remove("__CARGO_FIX_PLZ")
+        .output()
+        .map_err(|e| format!("could not run `cargo metadata`: {e}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "`cargo metadata` failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    #[derive(Deserialize)]
+    struct CargoMetadata {
+        workspace_root: PathBuf,
+    }
+
+    let metadata: CargoMetadata = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("invalid `cargo metadata` output: {e}"))?;
+
+    Ok(metadata.workspace_root)
+}
+
+/// The `[macros]` section of the loaded config, or `None` if the method that loaded it reported
+/// the file as missing. [`Config::try_get()`] uses the default config instead.
+///
+/// Other errors are returned as a message including the source error, which for a TOML
+/// error gives the line and column of the mistake.
+fn macros_config(
+    config: Result<&'static Config, ConfigError>,
+) -> Result<Option<&'static sqlx_core::config::macros::Config>, String> {
+    match config {
+        Ok(config) => Ok(Some(&config.macros)),
+        // A missing `sqlx.toml` is not an error; the macros just use their defaults.
+        Err(e) if e.is_missing() => Ok(None),
+        Err(e) => Err(match std::error::Error::source(&e) {
+            Some(source) => format!("failed to load sqlx config: {e}: {source}"),
+            None => format!("failed to load sqlx config: {e}"),
+        }),
+    }
+}
+
+fn url_vars(config: Option<&sqlx_core::config::macros::Config>) -> Vec<&str> {
+    match config.and_then(|config| config.database_url_var.as_deref()) {
+        Some(vars) if !vars.is_empty() => vars.iter().map(String::as_str).collect(),
+        _ => vec!["DATABASE_URL"],
+    }
+}
+
+/// Whether to build offline: `SQLX_OFFLINE` if set, otherwise `macros.offline`.
+fn offline(var: Option<String>, config: Option<&sqlx_core::config::macros::Config>) -> bool {
+    match var {
+        Some(s) => is_true(&s),
+        None => config.and_then(|config| config.offline).unwrap_or(false),
+    }
+}
+
+fn offline_fallback(
+    var: Option<String>,
+    config: Option<&sqlx_core::config::macros::Config>,
+) -> bool {
+    match var {
+        Some(s) => is_true(&s),
+        None => config
+            .and_then(|config| config.offline_fallback)
+            .unwrap_or(false),
+    }
+}
+
+fn is_true(var: &str) -> bool {
+    var.eq_ignore_ascii_case("true") || var == "1"
+}
+
+// If we are in a workspace, lookup `workspace_root` since `CARGO_MANIFEST_DIR` won't
+// reflect the workspace dir: https://github.com/rust-lang/cargo/issues/3946
+static METADATA: Lazy<Result<Metadata, String>> = Lazy::new(|| {
+    let manifest_dir: PathBuf = env("CARGO_MANIFEST_DIR")
+        .map_err(|_| "`CARGO_MANIFEST_DIR` must be set")?
+        .into();
+
+    // If a .env file exists at CARGO_MANIFEST_DIR, load environment variables from this,
+    // otherwise fallback to default dotenv behaviour.
+    let env_path = manifest_dir.join(".env");
+
+    #[cfg_attr(not(procmacro2_semver_exempt), allow(unused_variables))]
+    let env_path = if env_path.exists() {
+        dotenvy::from_path(&env_path)
+            .map_err(|e| format!("failed to load environment from {env_path:?}, {e}"))?;
+
+        Some(env_path)
+    } else {
+        dotenvy::dotenv().ok()
+    };
+
+    // tell the compiler to watch the `.env` for changes, if applicable
+    #[cfg(procmacro2_semver_exempt)]
+    if let Some(env_path) = env_path.as_ref().and_then(|path| path.to_str()) {
+        proc_macro::tracked_path::path(env_path);
+    }
+
+    let config = macros_config(Config::try_get())?;
+
+    let offline = offline(env("SQLX_OFFLINE").ok(), config);
+
+    let offline_check = env("SQLX_OFFLINE_CHECK").map_or(false, |var| is_true(&var));
+
+    let no_save = env("SQLX_NO_SAVE").map_or(false, |var| is_true(&var));
+
+    let offline_fallback = offline_fallback(env("SQLX_OFFLINE_FALLBACK").ok(), config);
+
+    let database_url = url_vars(config).into_iter().find_map(|var| env(var).ok());
+
+    Ok(Metadata {
+        manifest_dir,
+        offline,
+        offline_check,
+        no_save,
+        offline_fallback,
+        database_url,
+        config,
+        workspace_root: OnceCell::new(),
+    })
+});
+
+#[test]
+fn test_malformed_config_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sqlx.toml");
+
+    std::fs::write(&path, "[macros]\ndatabase_url_var = FOO_DATABASE_URL\n").unwrap();
+
+    let err = macros_config(Config::try_get_with(|| Ok(path.clone()))).unwrap_err();
+
+    assert!(err.starts_with("failed to load sqlx config: error parsing config file"));
+    // the TOML error points at the mistake
+    assert!(err.contains("line 2"), "{err}");
+
+    let missing = dir.path().join("missing.toml");
+    assert!(Config::try_get_with_path(&missing)
+        .unwrap_err()
+        .is_missing());
+    assert!(macros_config(Config::try_get_with(|| Ok(missing)))
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_offline_precedence() {
+    let config = sqlx_core::config::macros::Config {
+        offline: Some(true),
+        ..Default::default()
+    };
+    let config = Some(&config);
+
+    assert!(!offline(None, None));
+    assert!(offline(None, config));
+    assert!(offline(Some("1".into()), None));
+    assert!(!offline(Some("false".into()), config));
+}
+
+#[test]
+fn test_offline_fallback_precedence() {
+    let config = sqlx_core::config::macros::Config {
+        offline_fallback: Some(true),
+        ..Default::default()
+    };
+    let config = Some(&config);
+
+    assert!(!offline_fallback(None, None));
+    assert!(offline_fallback(None, config));
+    assert!(offline_fallback(Some("true".into()), None));
+    assert!(!offline_fallback(Some("0".into()), config));
+}
+
+#[test]
+fn test_cargo_workspace_root() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    assert_eq!(
+        cargo_workspace_root(manifest_dir).unwrap(),
+        manifest_dir.parent().unwrap()
+    );
+
+    let err = cargo_workspace_root(Path::new("/")).unwrap_err();
+    assert!(err.contains("`cargo metadata` failed"), "{err}");
+}
@@ -0,0 +1,121 @@
+//! The check for `macros.check_migrations`.
+
+use std::path::Path;
+
+use once_cell::sync::OnceCell;
+use sqlx_core::config::Config;
+use sqlx_core::migrate::resolve_blocking;
+
+use crate::database::DatabaseExt;
+use crate::query::metadata::Metadata;
+
+/// Check that the database at `database_url` has the latest migration applied.
+///
+/// This only connects the first time it's called in the compiler process; the result is reused
+/// for the remaining queries.
+pub fn check<DB: DatabaseExt>(database_url: &str) -> crate::Result<()> {
+    static RESULT: OnceCell<Result<(), String>> = OnceCell::new();
+
+    RESULT
+        .get_or_init(|| {
+            let config = &Config::get().migrate;
+
+            let dir = config
+                .migrations_dir
+                .as_deref()
+                .unwrap_or("migrations".as_ref());
+
+            let dir = Metadata::get().manifest_dir.join(dir);
+
+            let latest = latest_migration(&dir)?;
+
+            let table_name = config.table_name.as_deref().unwrap_or("_sqlx_migrations");
+
+            let applied = DB::list_applied_migrations_blocking(database_url, table_name)
+                .map_err(|e| {
+                    format!(
+                        "failed to check the database against the migrations in {}: {e}; \
+                         run migrations before building, or disable `macros.check_migrations`",
+                        dir.display()
+                    )
+                })?
+                .iter()
+                .map(|migration| migration.version)
+                .max();
+
+            check_version(applied, latest)
+        })
+        .clone()
+        .map_err(Into::into)
+}
+
+/// The version of the newest up (or simple) migration in `dir`, if there are any.
+fn latest_migration(dir: &Path) -> Result<Option<i64>, String> {
+    let migrations = resolve_blocking(dir).map_err(|e| e.to_string())?;
+
+    Ok(migrations
+        .iter()
+        .filter(|(migration, _)| !migration.migration_type.is_down_migration())
+        .map(|(migration, _)| migration.version)
+        .max())
+}
+
+fn check_version(applied: Option<i64>, latest: Option<i64>) -> Result<(), String> {
+    match (applied, latest) {
+        (_, None) => Ok(()),
+        (Some(applied), Some(latest)) if applied == latest => Ok(()),
+        (Some(applied), Some(latest)) if applied > latest => Err(format!(
+            "database is at migration {applied} but the latest is {latest}; \
+             the database has migrations that are not in this crate"
+        )),
+        (Some(applied), Some(latest)) => Err(format!(
+            "database is at migration {applied} but the latest is {latest}; \
+             run migrations before building"
+        )),
+        (None, Some(latest)) => Err(format!(
+            "database has no migrations applied but the latest is {latest}; \
+             run migrations before building"
+        )),
+    }
+}
+
+#[test]
+fn test_check_version() {
+    assert_eq!(check_version(Some(2), Some(2)), Ok(()));
+    assert_eq!(check_version(None, None), Ok(()));
+    assert_eq!(check_version(Some(1), None), Ok(()));
+
+    assert_eq!(
+        check_version(Some(1), Some(2)),
+        Err(
+            "database is at migration 1 but the latest is 2; run migrations before building".into()
+        )
+    );
+    assert_eq!(
+        check_version(None, Some(2)),
+        Err(
+            "database has no migrations applied but the latest is 2; run migrations before building"
+                .into()
+        )
+    );
+    assert!(check_version(Some(3), Some(2))
+        .unwrap_err()
+        .contains("not in this crate"));
+}
+
+#[test]
+fn test_latest_migration() {
+    let dir = tempfile::tempdir().unwrap();
+
+    assert_eq!(latest_migration(dir.path()), Ok(None));
+
+    for (file, sql) in [
+        ("20240101000000_init.sql", "CREATE TABLE foo (id INT);"),
+        ("20240201000000_bar.up.sql", "CREATE TABLE bar (id INT);"),
+        ("20240201000000_bar.down.sql", "DROP TABLE bar;"),
+    ] {
+        std::fs::write(dir.path().join(file), sql).unwrap();
+    }
+
+    assert_eq!(latest_migration(dir.path()), Ok(Some(20240201000000)));
+}
@@ -0,0 +1,259 @@
+//! Hand-written describe data for `macros.mock_describe`.
+
+use std::fs;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::Type;
+
+use crate::database::DatabaseExt;
+use crate::query::input::RecordType;
+use crate::query::metadata::Metadata;
+use crate::query::output::{self, ColumnType, RustColumn};
+use crate::query::{args, quote_expansion, quote_record, take_columns, QueryMacroInput};
+
+/// The contents of the `mock_describe` file.
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MockFile {
+    /// The database the queries are for, as named by `Database::NAME`,
+    /// e.g. `"PostgreSQL"`, `"MySQL"` or `"SQLite"`.
+    database: String,
+    queries: Vec<MockQuery>,
+}
+
+#[derive(serde::Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct MockQuery {
+    /// The query text; `*` matches any sequence of characters.
+    ///
+    /// Runs of whitespace compare equal to a single space.
+    query: String,
+    /// The Rust type of each bind parameter.
+    #[serde(default)]
+    params: Vec<String>,
+    #[serde(default)]
+    columns: Vec<MockColumn>,
+    #[serde(skip)]
+    pub(super) db_name: String,
+}
+
+#[derive(serde::Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+struct MockColumn {
+    name: String,
+    #[serde(rename = "type")]
+    type_: String,
+    #[serde(default)]
+    nullable: bool,
+}
+
+/// Find the mock for `sql`, if `macros.mock_describe` is set and any of its patterns match.
+pub fn find(metadata: &Metadata, sql: &str) -> crate::Result<Option<MockQuery>> {
+    let Some(path) = metadata
+        .config
+        .and_then(|config| config.mock_describe.as_ref())
+    else {
+        return Ok(None);
+    };
+
+    let path = metadata.manifest_dir.join(path);
+
+    let file = fs::read_to_string(&path).map_err(|e| {
+        format!(
+            "failed to read `mock_describe` file {}: {e}",
+            path.display()
+        )
+    })?;
+
+    let file: MockFile = serde_json::from_str(&file).map_err(|e| {
+        format!(
+            "failed to parse `mock_describe` file {}: {e}",
+            path.display()
+        )
+    })?;
+
+    let sql = normalize_whitespace(sql);
+
+    Ok(file
+        .queries
+        .into_iter()
+        .find(|mock| pattern_matches(&normalize_whitespace(&mock.query), &sql))
+        .map(|mock| MockQuery {
+            db_name: file.database.clone(),
+            ..mock
+        }))
+}
+
+/// Expand `input` from `mock` alone, without a database or cached data.
+///
+/// For the checked macros, the argument types are checked against the mock's parameter types, and the columns are
+/// decoded with runtime type checks since the mock can't guarantee them.
+pub fn expand<DB: DatabaseExt>(
+    input: QueryMacroInput,
+    mock: &MockQuery,
+) -> crate::Result<TokenStream> {
+    if input.checked {
+        args::check_arg_count(&input, mock.params.len(), Some("`mock_describe`"))?;
+    }
+
+    let mut args_tokens = args::quote_args(
+        &input,
+        &sqlx_core::describe::Describe::<DB> {
+            columns: Vec::new(),
+            parameters: None,
+            nullable: Vec::new(),
+        },
+    )?;
+
+    if input.checked {
+        for (i, (param_ty, expr)) in mock.params.iter().zip(&input.arg_exprs).enumerate() {
+            if args::get_type_override(expr).is_some() {
+                continue;
+            }
+
+            let param_ty = parse_type(param_ty)?.to_token_stream();
+
+            args_tokens.extend(args::quote_type_check(
+                &format_ident!("arg{}", i),
+                expr,
+                &param_ty,
+            ));
+        }
+    }
+
+    let columns = mock
+        .columns
+        .iter()
+        .map(|column| -> crate::Result<_> {
+            let ident = output::parse_ident(&column.name)?;
+            let type_ = parse_type(&column.type_)?;
+
+            Ok(RustColumn {
+                var_name: format_ident!("sqlx_query_as_{}", ident),
+                ident,
+                name: column.name.clone(),
+                type_: ColumnType::Checked(if column.nullable {
+                    quote! { ::std::option::Option<#type_> }
+                } else {
+                    quote! { #type_ }
+                }),
+                override_type: None,
+            })
+        })
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    let query_args = format_ident!("query_args");
+    let db_path = DB::db_path();
+    let sql = &input.sql;
+
+    let output = if columns.is_empty() {
+        quote! {
+            ::sqlx::__query_with_result::<#db_path, _>(#sql, #query_args)
+        }
+    } else {
+        match input.record_type {
+            RecordType::Generated => {
+                let record_name: Type = syn::parse_str("Record").unwrap();
+
+                let mut record_tokens = quote_record(&record_name, &columns)?;
+
+                record_tokens.extend(output::quote_query_as::<DB>(
+                    &input,
+                    &record_name,
+                    &query_args,
+                    &columns,
+                ));
+
+                record_tokens
+            }
+            RecordType::Given(ref out_ty) => {
+                let columns = take_columns(&input, columns)?;
+
+                output::quote_query_as::<DB>(&input, out_ty, &query_args, &columns)
+            }
+            RecordType::Tuple => output::quote_query_tuple::<DB>(&input, &query_args, &columns)?,
+            RecordType::Scalar => {
+                let [column] = &columns[..] else {
+                    return Err(syn::Error::new(
+                        input.src_span,
+                        format!("expected exactly 1 column, got {}", columns.len()),
+                    )
+                    .into());
+                };
+
+                let ty = &column.type_;
+
+                quote! {
+                    ::sqlx::__query_scalar_with_result::<#db_path, #ty, _>(#sql, #query_args)
+                }
+            }
+        }
+    };
+
+    quote_expansion(&input, None, args_tokens, output)
+}
+
+fn parse_type(ty: &str) -> crate::Result<Type> {
+    syn::parse_str(ty)
+        .map_err(|e| format!("invalid Rust type {ty:?} in `mock_describe`: {e}").into())
+}
+
+fn normalize_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Match `text` against `pattern`, where `*` matches any sequence of characters.
+fn pattern_matches(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+
+    // there's always at least one part
+    let first = parts.next().unwrap_or_default();
+
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let mut parts = parts.collect::<Vec<_>>();
+
+    // no `*` in the pattern
+    let Some(last) = parts.pop() else {
+        return rest.is_empty();
+    };
+
+    for part in parts {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[test]
+fn test_pattern_matches() {
+    assert!(pattern_matches("SELECT 1", "SELECT 1"));
+    assert!(!pattern_matches("SELECT 1", "SELECT 12"));
+    assert!(pattern_matches(
+        "SELECT * FROM users",
+        "SELECT id, name FROM users"
+    ));
+    assert!(!pattern_matches(
+        "SELECT * FROM users",
+        "SELECT id FROM users_old"
+    ));
+    assert!(pattern_matches(
+        "*FROM users*",
+        "SELECT id FROM users WHERE id = $1"
+    ));
+    assert!(pattern_matches("*", ""));
+    assert!(pattern_matches("a*a", "aa"));
+    assert!(!pattern_matches("a*a", "a"));
+    assert!(!pattern_matches("ab*ba", "aba"));
+
+    assert_eq!(
+        normalize_whitespace("SELECT id,\n       name\n  FROM users"),
+        "SELECT id, name FROM users"
+    );
+}
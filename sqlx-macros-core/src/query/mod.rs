@@ -1,9 +1,5 @@
-use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
-use std::{fs, io};
-
-use once_cell::sync::Lazy;
 use proc_macro2::TokenStream;
+use syn::ext::IdentExt;
 use syn::Type;
 
 pub use input::QueryMacroInput;
@@ -14,19 +10,32 @@ use sqlx_core::{column::Column, describe::Describe, type_info::TypeInfo};
 use crate::database::DatabaseExt;
 use crate::query::data::{hash_string, DynQueryData, QueryData};
 use crate::query::input::RecordType;
+use crate::query::metadata::Metadata;
 use either::Either;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
 use url::Url;
 
 mod args;
+pub mod cache;
 mod data;
+mod describe_proxy;
+mod digest;
 mod input;
+mod metadata;
+#[cfg(feature = "migrate")]
+mod migrations;
+mod mock;
 mod output;
+mod placeholders;
 
 #[derive(Copy, Clone)]
 pub struct QueryDriver {
     db_name: &'static str,
     url_schemes: &'static [&'static str],
     expand: fn(QueryMacroInput, QueryDataSource) -> crate::Result<TokenStream>,
+    describe: fn(&str, &str) -> crate::Result<String>,
+    matches_url: Option<fn(&Url) -> bool>,
 }
 
 impl QueryDriver {
@@ -38,15 +47,176 @@ impl QueryDriver {
             db_name: DB::NAME,
             url_schemes: DB::URL_SCHEMES,
             expand: expand_with::<DB>,
+            describe: describe_with::<DB>,
+            matches_url: None,
+        }
+    }
+
+    /// Only match database URLs for which `matches_url` returns `true`, in addition to
+    /// having one of the driver's [URL schemes][Self::matches_scheme()].
+    ///
+    /// This lets a driver look at the rest of the URL, e.g. its query parameters, to claim or
+    /// reject it, including to share a URL scheme with another driver.
+    pub const fn with_url_matcher(mut self, matches_url: fn(&Url) -> bool) -> Self {
+        self.matches_url = Some(matches_url);
+        self
+    }
+
+    /// The name of the database, as given by `Database::NAME`.
+    pub fn db_name(&self) -> &'static str {
+        self.db_name
+    }
+
+    /// The URL schemes of the database, as given by `Database::URL_SCHEMES`.
+    pub fn url_schemes(&self) -> &'static [&'static str] {
+        self.url_schemes
+    }
+
+    /// Whether `scheme` is one of [`Self::url_schemes()`], or an alias of one.
+    ///
+    /// See [`SCHEME_ALIASES`].
+    pub fn matches_scheme(&self, scheme: &str) -> bool {
+        let scheme = canonical_scheme(scheme);
+
+        self.url_schemes
+            .iter()
+            .any(|s| canonical_scheme(s) == scheme)
+    }
+
+    /// Whether the driver handles the database at `url`: its scheme must
+    /// [match][Self::matches_scheme()], and so must the rest of the URL if the driver set a
+    /// [matcher][Self::with_url_matcher()].
+    pub fn matches_url(&self, url: &Url) -> bool {
+        self.matches_scheme(url.scheme()) && self.matches_url.map_or(true, |matches| matches(url))
+    }
+}
+
+/// URL schemes that are spelled more than one way, as `(alias, scheme)` pairs.
+///
+/// A driver listing either spelling matches URLs with both. Only spellings of the same
+/// database belong here; `mariadb` isn't an alias of `mysql` since a separate driver
+/// could claim it.
+pub const SCHEME_ALIASES: &[(&str, &str)] = &[("postgresql", "postgres")];
+
+fn canonical_scheme(scheme: &str) -> &str {
+    SCHEME_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == scheme)
+        .map_or(scheme, |(_, scheme)| scheme)
+}
+
+impl std::fmt::Debug for QueryDriver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueryDriver")
+            .field("db_name", &self.db_name())
+            .field("url_schemes", &self.url_schemes())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Formats as the database name followed by its URL schemes, e.g. `PostgreSQL (postgres, postgresql)`.
+impl std::fmt::Display for QueryDriver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.db_name(), self.url_schemes().join(", "))
+    }
+}
+
+static REGISTERED_DRIVERS: Mutex<Vec<QueryDriver>> = Mutex::new(Vec::new());
+
+/// Make `driver` available to [`expand_input()`] for the remainder of the compiler process,
+/// in addition to the drivers passed to it.
+///
+/// This is for proc-macro crates wrapping these macros for an out-of-tree database, which
+/// should call it before the first query is expanded. Drivers passed to `expand_input()` are
+/// tried first, and take the place of registered drivers with the same name.
+///
+/// Returns `false` and does nothing if a driver with the same [`db_name()`][QueryDriver::db_name]
+/// was already registered.
+pub fn register_driver(driver: QueryDriver) -> bool {
+    let mut registered = REGISTERED_DRIVERS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    if registered.iter().any(|d| d.db_name == driver.db_name) {
+        return false;
+    }
+
+    registered.push(driver);
+    true
+}
+
+/// The drivers added with [`register_driver()`], in order.
+pub fn registered_drivers() -> Vec<QueryDriver> {
+    REGISTERED_DRIVERS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// `drivers`, followed by the [registered drivers][register_driver()] with other names.
+fn with_registered<'a>(drivers: impl IntoIterator<Item = &'a QueryDriver>) -> Vec<QueryDriver> {
+    let mut drivers = drivers.into_iter().copied().collect::<Vec<_>>();
+
+    for driver in registered_drivers() {
+        if !drivers.iter().any(|d| d.db_name == driver.db_name) {
+            drivers.push(driver);
         }
     }
+
+    drivers
 }
+
+/// Describe `sql` against the database at `database_url`, without expanding a macro.
+///
+/// Returns the query data as JSON in the format of the `query-<hash>.json` files written by
+/// `cargo sqlx prepare`, with the column names, types and nullability the macros would see.
+/// This is meant for tooling like code generators, e.g. called from a build script.
+///
+/// The driver is picked by the URL scheme, from `drivers` and the
+/// [registered drivers][register_driver()], as for [`expand_input()`].
+/// `sqlx.toml` is not read, so `macros.describe_proxy_command` does not apply.
+pub fn describe_json<'a>(
+    sql: &str,
+    database_url: &str,
+    drivers: impl IntoIterator<Item = &'a QueryDriver>,
+) -> crate::Result<String> {
+    let drivers = with_registered(drivers);
+
+    let data_source = QueryDataSource::live(database_url)?;
+
+    let Some(driver) = data_source.select_driver(&drivers)? else {
+        return Err(format!(
+            "no database driver found matching URL scheme {:?}",
+            data_source.scheme().unwrap_or_default()
+        )
+        .into());
+    };
+
+    (driver.describe)(sql, database_url)
+}
+
+fn describe_with<DB: DatabaseExt>(sql: &str, database_url: &str) -> crate::Result<String>
+where
+    Describe<DB>: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let describe = DB::describe_blocking(sql, database_url)?;
+
+    QueryData::from_describe(sql, describe).to_json()
+}
+
 pub enum QueryDataSource<'a> {
     Live {
         database_url: &'a str,
         database_url_parsed: Url,
     },
     Cached(DynQueryData),
+    /// Cached data to compare against a fresh description of the query, with
+    /// `SQLX_OFFLINE_CHECK`.
+    Check {
+        cached: DynQueryData,
+        database_url: &'a str,
+    },
+    Mock(mock::MockQuery),
 }
 
 impl<'a> QueryDataSource<'a> {
@@ -57,142 +227,206 @@ impl<'a> QueryDataSource<'a> {
         })
     }
 
-    pub fn matches_driver(&self, driver: &QueryDriver) -> bool {
+    /// The database the query data is for, as named by `Database::NAME`.
+    ///
+    /// `None` for [`Self::Live`], which is identified by its [URL scheme][Self::scheme()] instead.
+    pub fn db_name(&self) -> Option<&str> {
+        match self {
+            Self::Live { .. } => None,
+            Self::Cached(dyn_data)
+            | Self::Check {
+                cached: dyn_data, ..
+            } => Some(&dyn_data.db_name),
+            Self::Mock(mock) => Some(&mock.db_name),
+        }
+    }
+
+    /// The scheme of the database URL for [`Self::Live`], `None` otherwise.
+    pub fn scheme(&self) -> Option<&str> {
         match self {
             Self::Live {
                 database_url_parsed,
                 ..
-            } => driver.url_schemes.contains(&database_url_parsed.scheme()),
-            Self::Cached(dyn_data) => dyn_data.db_name == driver.db_name,
+            } => Some(database_url_parsed.scheme()),
+            Self::Cached(_) | Self::Check { .. } | Self::Mock(_) => None,
         }
     }
-}
-
-struct Metadata {
-    #[allow(unused)]
-    manifest_dir: PathBuf,
-    offline: bool,
-    database_url: Option<String>,
-    workspace_root: Arc<Mutex<Option<PathBuf>>>,
-}
 
-impl Metadata {
-    pub fn workspace_root(&self) -> PathBuf {
-        let mut root = self.workspace_root.lock().unwrap();
-        if root.is_none() {
-            use serde::Deserialize;
-            use std::process::Command;
+    pub fn matches_driver(&self, driver: &QueryDriver) -> bool {
+        match self {
+            Self::Live {
+                database_url_parsed,
+                ..
+            } => driver.matches_url(database_url_parsed),
+            _ => self.db_name() == Some(driver.db_name),
+        }
+    }
 
-            let cargo = env("CARGO").expect("`CARGO` must be set");
+    /// The one driver in `drivers` that [matches][Self::matches_driver()], if any.
+    ///
+    /// Errors if more than one does, e.g. if two drivers claim the same URL scheme, rather
+    /// than letting the order of `drivers` decide.
+    pub fn select_driver<'d>(
+        &self,
+        drivers: &'d [QueryDriver],
+    ) -> crate::Result<Option<&'d QueryDriver>> {
+        let matching = drivers
+            .iter()
+            .filter(|driver| self.matches_driver(driver))
+            .collect::<Vec<_>>();
 
-            let output = Command::new(cargo)
-                .args(["metadata", "--format-version=1", "--no-deps"])
-                .current_dir(&self.manifest_dir)
-                .env_remove("__CARGO_FIX_PLZ")
-                .output()
-                .expect("Could not fetch metadata");
+        match matching[..] {
+            [] => Ok(None),
+            [driver] => Ok(Some(driver)),
+            _ => {
+                let matched = match (self.scheme(), self.db_name()) {
+                    (Some(scheme), _) => format!("URL scheme {scheme:?}"),
+                    (None, db_name) => format!("database {:?}", db_name.unwrap_or_default()),
+                };
 
-            #[derive(Deserialize)]
-            struct CargoMetadata {
-                workspace_root: PathBuf,
+                Err(format!(
+                    "multiple database drivers match {matched}: {}",
+                    matching
+                        .iter()
+                        .map(|driver| driver.db_name())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+                .into())
             }
-
-            let metadata: CargoMetadata =
-                serde_json::from_slice(&output.stdout).expect("Invalid `cargo metadata` output");
-
-            *root = Some(metadata.workspace_root);
         }
-        root.clone().unwrap()
     }
 }
 
-// If we are in a workspace, lookup `workspace_root` since `CARGO_MANIFEST_DIR` won't
-// reflect the workspace dir: https://github.com/rust-lang/cargo/issues/3946
-static METADATA: Lazy<Metadata> = Lazy::new(|| {
-    let manifest_dir: PathBuf = env("CARGO_MANIFEST_DIR")
-        .expect("`CARGO_MANIFEST_DIR` must be set")
-        .into();
+pub fn expand_input<'a>(
+    mut input: QueryMacroInput,
+    drivers: impl IntoIterator<Item = &'a QueryDriver>,
+) -> crate::Result<TokenStream> {
+    let metadata =
+        Metadata::try_get().map_err(|e| syn::Error::new(input.src_span, e.to_string()))?;
 
-    // If a .env file exists at CARGO_MANIFEST_DIR, load environment variables from this,
-    // otherwise fallback to default dotenv behaviour.
-    let env_path = manifest_dir.join(".env");
+    if let Some(params) = &input.params {
+        let enabled = |flag: fn(&sqlx_core::config::macros::Config) -> bool| {
+            metadata.config.map_or(false, flag)
+        };
 
-    #[cfg_attr(not(procmacro2_semver_exempt), allow(unused_variables))]
-    let env_path = if env_path.exists() {
-        let res = dotenvy::from_path(&env_path);
-        if let Err(e) = res {
-            panic!("failed to load environment from {env_path:?}, {e}");
+        if !enabled(|config| config.enable_params_struct) {
+            return Err(syn::Error::new_spanned(
+                params,
+                "`params = ..` requires `enable_params_struct = true` \
+                 in the `[macros]` section of `sqlx.toml`",
+            )
+            .into());
         }
 
-        Some(env_path)
-    } else {
-        dotenvy::dotenv().ok()
-    };
-
-    // tell the compiler to watch the `.env` for changes, if applicable
-    #[cfg(procmacro2_semver_exempt)]
-    if let Some(env_path) = env_path.as_ref().and_then(|path| path.to_str()) {
-        proc_macro::tracked_path::path(env_path);
+        if !enabled(|config| config.enable_named_params) {
+            return Err(syn::Error::new_spanned(
+                params,
+                "`params = ..` binds fields to `$name` placeholders, which require \
+                 `enable_named_params = true` in the `[macros]` section of `sqlx.toml`",
+            )
+            .into());
+        }
     }
 
-    let offline = env("SQLX_OFFLINE")
-        .map(|s| s.eq_ignore_ascii_case("true") || s == "1")
-        .unwrap_or(false);
-
-    let database_url = env("DATABASE_URL").ok();
+    if metadata
+        .config
+        .map_or(false, |config| config.enable_named_params)
+    {
+        input.number_named_params()?;
+    }
 
-    Metadata {
-        manifest_dir,
-        offline,
-        database_url,
-        workspace_root: Arc::new(Mutex::new(None)),
+    if let Some(path) = metadata
+        .config
+        .and_then(|config| config.query_digest_path.as_ref())
+    {
+        digest::record(&metadata.manifest_dir.join(path), &input.sql)?;
     }
-});
 
-pub fn expand_input<'a>(
-    input: QueryMacroInput,
-    drivers: impl IntoIterator<Item = &'a QueryDriver>,
-) -> crate::Result<TokenStream> {
-    let data_source = match &*METADATA {
-        Metadata {
-            offline: false,
-            database_url: Some(db_url),
-            ..
-        } => QueryDataSource::live(db_url)?,
-
-        Metadata { offline, .. } => {
-            // Try load the cached query metadata file.
-            let filename = format!("query-{}.json", hash_string(&input.sql));
-
-            // Check SQLX_OFFLINE_DIR, then local .sqlx, then workspace .sqlx.
-            let dirs = [
-                || env("SQLX_OFFLINE_DIR").ok().map(PathBuf::from),
-                || Some(METADATA.manifest_dir.join(".sqlx")),
-                || Some(METADATA.workspace_root().join(".sqlx")),
-            ];
-            let Some(data_file_path) = dirs
+    let mut drivers = with_registered(drivers);
+
+    let registered = match &drivers[..] {
+        [] => "no drivers are enabled".to_string(),
+        drivers => format!(
+            "enabled drivers: {}",
+            drivers
                 .iter()
-                .filter_map(|path| path())
-                .map(|path| path.join(&filename))
-                .find(|path| path.exists())
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+
+    // With a `#[driver]` hint, the URL comes from the driver's variable in `driver_url_vars`.
+    let (database_url, url_vars) = match &input.driver {
+        Some(driver) => {
+            let scheme = driver.value();
+
+            let Some(var) = metadata
+                .config
+                .and_then(|config| config.driver_url_vars.get(&scheme))
             else {
-                return Err(
-                    if *offline {
-                        "`SQLX_OFFLINE=true` but there is no cached data for this query, run `cargo sqlx prepare` to update the query cache or unset `SQLX_OFFLINE`"
-                    } else {
-                        "set `DATABASE_URL` to use query macros online, or run `cargo sqlx prepare` to update the query cache"
-                    }.into()
-                );
+                return Err(syn::Error::new_spanned(
+                    driver,
+                    format!(
+                        "`#[driver = {scheme:?}]` requires `{scheme}` in the \
+                         `[macros.driver_url_vars]` section of `sqlx.toml`"
+                    ),
+                )
+                .into());
             };
 
-            QueryDataSource::Cached(DynQueryData::from_data_file(&data_file_path, &input.sql)?)
+            drivers.retain(|d| d.matches_scheme(&scheme));
+
+            if drivers.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    driver,
+                    format!("no enabled driver has the URL scheme {scheme:?} ({registered})"),
+                )
+                .into());
+            }
+
+            (env(var).ok(), vec![&**var])
         }
+        None => (metadata.database_url.clone(), metadata.url_vars()),
     };
 
-    for driver in drivers {
-        if data_source.matches_driver(driver) {
-            return (driver.expand)(input, data_source);
+    let data_source = if let Some(mock) = mock::find(metadata, &input.sql)? {
+        QueryDataSource::Mock(mock)
+    } else {
+        match (&database_url, metadata) {
+            (Some(db_url), Metadata { offline: false, .. }) => QueryDataSource::live(db_url)?,
+
+            (_, Metadata { offline, .. }) => {
+                // Try load the cached query metadata.
+                let Some(data) = load_cached(&input)? else {
+                    return Err(if *offline {
+                        "offline mode is enabled (by `SQLX_OFFLINE=true` or `offline = true` in the `[macros]` section of `sqlx.toml`) but there is no cached data for this query, run `cargo sqlx prepare` to update the query cache or set `SQLX_OFFLINE=false`".into()
+                    } else {
+                        let vars = match &url_vars[..] {
+                            [var] => format!("`{var}`"),
+                            vars => format!("one of `{}`", vars.join("`, `")),
+                        };
+
+                        format!(
+                            "set {vars} to use query macros online, or run `cargo sqlx prepare` to update the query cache"
+                        ).into()
+                    });
+                };
+
+                match &database_url {
+                    Some(database_url) if metadata.offline_check => QueryDataSource::Check {
+                        cached: data,
+                        database_url,
+                    },
+                    _ => QueryDataSource::Cached(data),
+                }
+            }
         }
+    };
+
+    if let Some(driver) = data_source.select_driver(&drivers)? {
+        return (driver.expand)(input, data_source);
     }
 
     match data_source {
@@ -200,15 +434,21 @@ pub fn expand_input<'a>(
             database_url_parsed,
             ..
         } => Err(format!(
-            "no database driver found matching URL scheme {:?}; the corresponding Cargo feature may need to be enabled", 
+            "no database driver found matching URL scheme {:?}; the corresponding Cargo feature may need to be enabled ({registered})",
             database_url_parsed.scheme()
         ).into()),
-        QueryDataSource::Cached(data) => {
+        QueryDataSource::Cached(data) | QueryDataSource::Check { cached: data, .. } => {
             Err(format!(
-                "found cached data for database {:?} but no matching driver; the corresponding Cargo feature may need to be enabled",
+                "found cached data for database {:?} but no matching driver; the corresponding Cargo feature may need to be enabled ({registered})",
                 data.db_name
             ).into())
         }
+        QueryDataSource::Mock(mock) => {
+            Err(format!(
+                "found `mock_describe` data for database {:?} but no matching driver; the corresponding Cargo feature may need to be enabled ({registered})",
+                mock.db_name
+            ).into())
+        }
     }
 }
 
@@ -219,15 +459,182 @@ fn expand_with<DB: DatabaseExt>(
 where
     Describe<DB>: DescribeExt,
 {
+    let config = Metadata::get().config;
+
+    let mut fallback_reason = None;
+
     let (query_data, offline): (QueryData<DB>, bool) = match data_source {
+        QueryDataSource::Mock(mock) => return mock::expand::<DB>(input, &mock),
         QueryDataSource::Cached(dyn_data) => (QueryData::from_dyn_data(dyn_data)?, true),
+        QueryDataSource::Check {
+            cached,
+            database_url,
+        } => {
+            let data = QueryData::<DB>::from_dyn_data(cached)?;
+
+            let describe = DB::describe_blocking(&input.sql, &describe_url(database_url)?)?;
+
+            check_cached(&data, &describe)?;
+
+            (data, true)
+        }
         QueryDataSource::Live { database_url, .. } => {
-            let describe = DB::describe_blocking(&input.sql, database_url)?;
+            let database_url = describe_url(database_url)?;
+
+            let describe = DB::describe_blocking(&input.sql, &database_url);
+
+            if let Err(e) = &describe {
+                if Metadata::get().offline_fallback && is_unreachable(e) {
+                    if let Some(data) = load_cached(&input)? {
+                        eprintln!(
+                            "warning: using cached data for query because the database could \
+                             not be reached (`offline_fallback` is enabled): {e}"
+                        );
+
+                        return expand_with_data(
+                            input,
+                            QueryData::from_dyn_data(data)?,
+                            true,
+                            None,
+                        );
+                    }
+                }
+            }
+
+            if config.map_or(false, |c| c.check_migrations) {
+                #[cfg(feature = "migrate")]
+                migrations::check::<DB>(&database_url)?;
+
+                #[cfg(not(feature = "migrate"))]
+                return Err("`macros.check_migrations` requires the `migrate` feature".into());
+            }
+
+            let describe = match describe {
+                Ok(describe) => describe,
+                Err(e) if config.map_or(false, |c| c.describe_fallback) => {
+                    let describe = DB::describe_fallback_blocking(&input.sql, &database_url)
+                        .map_err(|_| e.to_string())?;
+                    fallback_reason = Some(e.to_string());
+                    describe
+                }
+                Err(e) => return Err(e.into()),
+            };
+
             (QueryData::from_describe(&input.sql, describe), false)
         }
     };
 
-    expand_with_data(input, query_data, offline)
+    let fallback = fallback_reason
+        .as_deref()
+        .map(|reason| output::DescribeFallback {
+            reason,
+            column_type: config.and_then(|c| c.describe_fallback_type.as_deref()),
+        });
+
+    expand_with_data(input, query_data, offline, fallback.as_ref())
+}
+
+/// The URL to describe queries with, setting `macros.search_path` and going through
+/// `macros.describe_proxy_command` if they're set.
+fn describe_url(database_url: &str) -> crate::Result<String> {
+    let config = Metadata::get().config;
+
+    let database_url = match config.and_then(|c| c.search_path.as_deref()) {
+        Some(search_path) => with_search_path(database_url, search_path)?,
+        None => database_url.to_owned(),
+    };
+
+    match config.and_then(|c| c.describe_proxy_command.as_ref()) {
+        Some(proxy) => describe_proxy::proxied_url(proxy, &database_url),
+        None => Ok(database_url),
+    }
+}
+
+/// Load the cached data for `input`, also looking next to the query file for `query_file!()`.
+fn load_cached(input: &QueryMacroInput) -> crate::Result<Option<DynQueryData>> {
+    let hash = hash_string(&input.sql);
+
+    match &input.file_path {
+        Some(file_path) => {
+            cache::query_cache().load_for_file(&hash, &input.sql, Path::new(file_path))
+        }
+        None => cache::query_cache().load(&hash, &input.sql),
+    }
+}
+
+/// Whether `error` from describing a query means the database couldn't be reached at all,
+/// for `macros.offline_fallback`.
+fn is_unreachable(error: &sqlx_core::Error) -> bool {
+    matches!(
+        error,
+        sqlx_core::Error::Io(_) | sqlx_core::Error::Tls(_) | sqlx_core::Error::PoolTimedOut
+    )
+}
+
+/// Add `search_path` to the startup options in a Postgres `database_url`.
+fn with_search_path(database_url: &str, search_path: &str) -> crate::Result<String> {
+    let mut url =
+        Url::parse(database_url).map_err(|e| format!("failed to parse database URL: {e}"))?;
+
+    if !matches!(url.scheme(), "postgres" | "postgresql") {
+        return Err(format!(
+            "`macros.search_path` is only supported for Postgres, got URL scheme {:?}",
+            url.scheme()
+        )
+        .into());
+    }
+
+    // spaces and backslashes in startup options must be escaped with a backslash
+    let search_path = search_path.replace('\\', "\\\\").replace(' ', "\\ ");
+
+    url.query_pairs_mut()
+        .append_pair("options[search_path]", &search_path);
+
+    Ok(url.into())
+}
+
+/// Error if the cached data for a query doesn't match `describe`, for `SQLX_OFFLINE_CHECK`.
+///
+/// Like `cargo sqlx prepare --check`, this compares the data as it's saved, so type info that
+/// differs between databases, e.g. the OIDs of custom types in Postgres, counts as a change.
+fn check_cached<DB: DatabaseExt>(
+    cached: &QueryData<DB>,
+    describe: &Describe<DB>,
+) -> crate::Result<()>
+where
+    Describe<DB>: DescribeExt,
+{
+    let to_value = |describe: &Describe<DB>| {
+        serde_json::to_value(describe).map_err(|e| format!("failed to serialize query data: {e}"))
+    };
+
+    let cached = to_value(&cached.describe)?;
+    let fresh = to_value(describe)?;
+
+    let changed = [
+        ("columns", "columns"),
+        ("parameters", "parameters"),
+        ("nullable", "nullability"),
+    ]
+    .into_iter()
+    .filter(|(key, _)| cached.get(key) != fresh.get(key))
+    .map(|(_, part)| part)
+    .collect::<Vec<_>>();
+
+    if changed.is_empty() && cached == fresh {
+        return Ok(());
+    }
+
+    let changed = match &changed[..] {
+        [] => "its description".to_string(),
+        changed => format!("its {}", changed.join(", ")),
+    };
+
+    Err(format!(
+        "cached data for this query is out of date: {changed} changed in the database \
+         (checked because `SQLX_OFFLINE_CHECK` is set), re-run `cargo sqlx prepare`"
+    )
+    .into())
 }
 
 // marker trait for `Describe` that lets us conditionally require it to be `Serialize + Deserialize`
@@ -242,12 +649,14 @@ fn expand_with_data<DB: DatabaseExt>(
     input: QueryMacroInput,
     data: QueryData<DB>,
     offline: bool,
+    fallback: Option<&output::DescribeFallback<'_>>,
 ) -> crate::Result<TokenStream>
 where
     Describe<DB>: DescribeExt,
 {
-    // validate at the minimum that our args match the query's input parameters
-    let num_parameters = match data.describe.parameters() {
+    // validate at the minimum that our args match the query's input parameters,
+    // unless this is an `*_unchecked!()` invocation
+    let num_parameters = match data.describe.parameters().filter(|_| input.checked) {
         Some(Either::Left(params)) => Some(params.len()),
         Some(Either::Right(num)) => Some(num),
 
@@ -255,17 +664,27 @@ where
     };
 
     if let Some(num) = num_parameters {
-        if num != input.arg_exprs.len() {
-            return Err(
-                format!("expected {} parameters, got {}", num, input.arg_exprs.len()).into(),
-            );
-        }
+        args::check_arg_count(&input, num, None)?;
     }
 
+    let param_count_assertion = Metadata::get()
+        .config
+        .filter(|config| config.assert_param_count && !input.checked)
+        .and_then(|_| placeholders::PlaceholderStyle::for_database(DB::NAME))
+        .and_then(|style| placeholders::count_placeholders(&input.sql, style))
+        .map(|count| placeholders::quote_assertion(count, input.arg_exprs.len(), input.src_span));
+
     let args_tokens = args::quote_args(&input, &data.describe)?;
 
     let query_args = format_ident!("query_args");
 
+    let query_key = output::QueryKey {
+        hash: &data.hash,
+        named: named_query(&input)?,
+        hint: &input.nullability_hint,
+        sql: &input.sql,
+    };
+
     let output = if data
         .describe
         .columns()
@@ -281,30 +700,11 @@ where
     } else {
         match input.record_type {
             RecordType::Generated => {
-                let columns = output::columns_to_rust::<DB>(&data.describe)?;
+                let columns = output::columns_to_rust::<DB>(&data.describe, &query_key, fallback)?;
 
                 let record_name: Type = syn::parse_str("Record").unwrap();
 
-                for rust_col in &columns {
-                    if rust_col.type_.is_wildcard() {
-                        return Err(
-                            "wildcard overrides are only allowed with an explicit record type, \
-                             e.g. `query_as!()` and its variants"
-                                .into(),
-                        );
-                    }
-                }
-
-                let record_fields = columns
-                    .iter()
-                    .map(|output::RustColumn { ident, type_, .. }| quote!(#ident: #type_,));
-
-                let mut record_tokens = quote! {
-                    #[derive(Debug)]
-                    struct #record_name {
-                        #(#record_fields)*
-                    }
-                };
+                let mut record_tokens = quote_record(&record_name, &columns)?;
 
                 record_tokens.extend(output::quote_query_as::<DB>(
                     &input,
@@ -316,62 +716,235 @@ where
                 record_tokens
             }
             RecordType::Given(ref out_ty) => {
-                let columns = output::columns_to_rust::<DB>(&data.describe)?;
+                let columns = take_columns(
+                    &input,
+                    output::columns_to_rust::<DB>(&data.describe, &query_key, fallback)?,
+                )?;
 
                 output::quote_query_as::<DB>(&input, out_ty, &query_args, &columns)
             }
-            RecordType::Scalar => {
-                output::quote_query_scalar::<DB>(&input, &query_args, &data.describe)?
-            }
+            RecordType::Tuple => output::quote_query_tuple::<DB>(
+                &input,
+                &query_args,
+                &output::columns_to_rust::<DB>(&data.describe, &query_key, fallback)?,
+            )?,
+            RecordType::Scalar => output::quote_query_scalar::<DB>(
+                &input,
+                &query_args,
+                &data.describe,
+                &query_key,
+                fallback,
+            )?,
         }
     };
 
-    let ret_tokens = quote! {
-        {
-            #[allow(clippy::all)]
-            {
-                use ::sqlx::Arguments as _;
+    let ret_tokens = quote_expansion(&input, param_count_assertion, args_tokens, output)?;
 
-                #args_tokens
+    // Store query metadata only if offline support is enabled but the current build is online.
+    // If the build is offline, the cache is our input so it's pointless to also write data for it.
+    // `SQLX_NO_SAVE` keeps the cache untouched even when online.
+    if !offline && !Metadata::get().no_save {
+        cache::query_cache().store(&data.hash, &data.to_json()?)?;
+    }
 
-                #output
+    Ok(ret_tokens)
+}
+
+/// The `Record` struct generated by `query!()` for `columns`, with its trait impls.
+fn quote_record(record_name: &Type, columns: &[output::RustColumn]) -> crate::Result<TokenStream> {
+    for rust_col in columns {
+        if rust_col.type_.is_wildcard() {
+            return Err(
+                "wildcard overrides are only allowed with an explicit record type, \
+                 e.g. `query_as!()` and its variants"
+                    .into(),
+            );
+        }
+    }
+
+    let record_fields = columns.iter().map(|column| {
+        let ident = &column.ident;
+        let type_ = column.field_type();
+        quote!(#ident: #type_,)
+    });
+
+    let config = Metadata::get().config;
+
+    let redacted_columns = config.map_or(&[][..], |config| &config.redacted_columns[..]);
+
+    let non_exhaustive = config
+        .map_or(false, |config| config.record_non_exhaustive)
+        .then(|| quote!(#[non_exhaustive]));
+
+    let derives = config
+        .map_or(&[][..], |config| &config.record_derives[..])
+        .iter()
+        .map(|derive| {
+            syn::parse_str::<syn::Path>(derive)
+                .map_err(|e| format!("invalid derive {derive:?} in `record_derives`: {e}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut record_tokens = if columns.iter().any(|col| col.is_listed_in(redacted_columns)) {
+        let debug_fields = columns.iter().map(|col| {
+            let ident = &col.ident;
+            let name = ident.unraw().to_string();
+
+            if col.is_listed_in(redacted_columns) {
+                quote!(.field(#name, &"***"))
+            } else {
+                quote!(.field(#name, &self.#ident))
+            }
+        });
+
+        quote! {
+            #[derive(#(#derives),*)]
+            #non_exhaustive
+            struct #record_name {
+                #(#record_fields)*
+            }
+
+            impl ::std::fmt::Debug for #record_name {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    f.debug_struct("Record")
+                        #(#debug_fields)*
+                        .finish()
+                }
+            }
+        }
+    } else {
+        quote! {
+            #[derive(Debug #(, #derives)*)]
+            #non_exhaustive
+            struct #record_name {
+                #(#record_fields)*
             }
         }
     };
 
-    // Store query metadata only if offline support is enabled but the current build is online.
-    // If the build is offline, the cache is our input so it's pointless to also write data for it.
-    if !offline {
-        // Only save query metadata if SQLX_OFFLINE_DIR is set manually or by `cargo sqlx prepare`.
-        // Note: in a cargo workspace this path is relative to the root.
-        if let Ok(dir) = env("SQLX_OFFLINE_DIR") {
-            let path = PathBuf::from(&dir);
-
-            match fs::metadata(&path) {
-                Err(e) => {
-                    if e.kind() != io::ErrorKind::NotFound {
-                        // Can't obtain information about .sqlx
-                        return Err(format!("{e}: {dir}").into());
-                    }
-                    // .sqlx doesn't exist.
-                    return Err(format!("sqlx offline path does not exist: {dir}").into());
+    let key_columns = config.map_or(&[][..], |config| &config.record_key_columns[..]);
+
+    let key_fields = columns
+        .iter()
+        .filter(|col| col.is_listed_in(key_columns))
+        .map(|col| &col.ident)
+        .collect::<Vec<_>>();
+
+    if !key_fields.is_empty() {
+        record_tokens.extend(quote! {
+            impl ::std::cmp::PartialEq for #record_name {
+                fn eq(&self, other: &Self) -> bool {
+                    true #(&& self.#key_fields == other.#key_fields)*
                 }
-                Ok(meta) => {
-                    if !meta.is_dir() {
-                        return Err(format!(
-                            "sqlx offline path exists, but is not a directory: {dir}"
-                        )
-                        .into());
-                    }
+            }
+
+            impl ::std::cmp::Eq for #record_name {}
 
-                    // .sqlx exists and is a directory, store data.
-                    data.save_in(path)?;
+            impl ::std::hash::Hash for #record_name {
+                fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                    #(::std::hash::Hash::hash(&self.#key_fields, state);)*
                 }
             }
+        });
+    }
+
+    Ok(record_tokens)
+}
+
+/// The `[macros.queries]` entry named by the query's `#[name]` hint, if it has one.
+fn named_query(
+    input: &QueryMacroInput,
+) -> crate::Result<Option<(&str, &'static sqlx_core::config::macros::QueryConfig)>> {
+    let Some(name) = &input.name else {
+        return Ok(None);
+    };
+
+    let name = name.value();
+
+    let Some((name, named)) = Metadata::get()
+        .config
+        .and_then(|config| config.queries.get_key_value(&name))
+    else {
+        return Err(syn::Error::new_spanned(
+            input.name.as_ref(),
+            format!("no `[macros.queries.{name}]` section in `sqlx.toml` for `#[name = {name:?}]`"),
+        )
+        .into());
+    };
+
+    Ok(Some((name, named)))
+}
+
+/// Keep only the leading columns requested by a `#[columns = N]` hint, if there is one.
+///
+/// The columns keep their positions in the row, so the `N` fields are still decoded from
+/// the first `N` columns.
+fn take_columns(
+    input: &QueryMacroInput,
+    mut columns: Vec<output::RustColumn>,
+) -> crate::Result<Vec<output::RustColumn>> {
+    if let Some((n, span)) = input.take_columns {
+        if n == 0 || n > columns.len() {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "`#[columns = {n}]` must be between 1 and the {} column(s) the query returns",
+                    columns.len()
+                ),
+            )
+            .into());
         }
+
+        columns.truncate(n);
     }
 
-    Ok(ret_tokens)
+    Ok(columns)
+}
+
+/// Wrap `output` into the final expansion, binding the arguments first.
+fn quote_expansion(
+    input: &QueryMacroInput,
+    prelude: Option<TokenStream>,
+    args_tokens: TokenStream,
+    output: TokenStream,
+) -> crate::Result<TokenStream> {
+    let output = match input.timeout {
+        Some((timeout, span)) => {
+            if !Metadata::get()
+                .config
+                .map_or(false, |c| c.enable_timeout_hints)
+            {
+                return Err(syn::Error::new(
+                    span,
+                    "`#[timeout]` hints require `enable_timeout_hints = true` \
+                     in the `[macros]` section of `sqlx.toml`",
+                )
+                .into());
+            }
+
+            let millis = timeout.as_millis() as u64;
+
+            quote! {
+                ({ #output }).timeout(::std::time::Duration::from_millis(#millis))
+            }
+        }
+        None => output,
+    };
+
+    Ok(quote! {
+        {
+            #[allow(clippy::all)]
+            {
+                use ::sqlx::Arguments as _;
+
+                #prelude
+
+                #args_tokens
+
+                #output
+            }
+        }
+    })
 }
 
 /// Get the value of an environment variable, telling the compiler about it if applicable.
@@ -386,3 +959,216 @@ fn env(name: &str) -> Result<String, std::env::VarError> {
         std::env::var(name)
     }
 }
+
+#[test]
+fn test_query_data_source_identity() {
+    let live = QueryDataSource::live("postgres://localhost/sqlx").unwrap();
+    assert_eq!(live.scheme(), Some("postgres"));
+    assert_eq!(live.db_name(), None);
+
+    let cached = QueryDataSource::Cached(DynQueryData {
+        db_name: "PostgreSQL".into(),
+        query: "SELECT 1".into(),
+        describe: serde_json::Value::Null,
+        hash: hash_string("SELECT 1"),
+    });
+    assert_eq!(cached.scheme(), None);
+    assert_eq!(cached.db_name(), Some("PostgreSQL"));
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn test_query_driver_display() {
+    let driver = QueryDriver::new::<sqlx_postgres::Postgres>();
+
+    assert_eq!(driver.db_name(), "PostgreSQL");
+    assert_eq!(driver.to_string(), "PostgreSQL (postgres, postgresql)");
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn test_register_driver() {
+    // the registry is shared by every test in the process, so register a driver that no other
+    // test uses and that can't be picked for any URL
+    let driver = QueryDriver {
+        db_name: "RegisterDriverTestSQL",
+        url_schemes: &[],
+        ..QueryDriver::new::<sqlx_postgres::Postgres>()
+    };
+
+    assert!(register_driver(driver));
+    assert!(!register_driver(driver));
+
+    let registered = registered_drivers();
+    assert_eq!(
+        registered
+            .iter()
+            .filter(|d| d.db_name() == "RegisterDriverTestSQL")
+            .count(),
+        1
+    );
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn test_select_driver_ambiguous() {
+    let postgres = QueryDriver::new::<sqlx_postgres::Postgres>();
+    let other = QueryDriver {
+        db_name: "OtherSQL",
+        ..postgres
+    };
+
+    let live = QueryDataSource::live("postgres://localhost/sqlx").unwrap();
+
+    let drivers = [postgres];
+    let selected = live.select_driver(&drivers).unwrap().unwrap();
+    assert_eq!(selected.db_name(), "PostgreSQL");
+
+    assert!(live.select_driver(&[]).unwrap().is_none());
+
+    let err = live.select_driver(&[postgres, other]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "multiple database drivers match URL scheme \"postgres\": PostgreSQL, OtherSQL"
+    );
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn test_scheme_aliases() {
+    let postgres = QueryDriver::new::<sqlx_postgres::Postgres>();
+    let only_postgresql = QueryDriver {
+        url_schemes: &["postgresql"],
+        ..postgres
+    };
+    let other = QueryDriver {
+        db_name: "OtherSQL",
+        url_schemes: &["other"],
+        ..postgres
+    };
+
+    for driver in [postgres, only_postgresql] {
+        assert!(driver.matches_scheme("postgres"));
+        assert!(driver.matches_scheme("postgresql"));
+        assert!(!driver.matches_scheme("other"));
+    }
+
+    assert!(!other.matches_scheme("postgres"));
+    assert!(!other.matches_scheme("postgresql"));
+
+    let live = QueryDataSource::live("postgres://localhost/sqlx").unwrap();
+    assert!(live.matches_driver(&only_postgresql));
+}
+
+#[test]
+fn test_is_unreachable() {
+    use std::io;
+
+    let refused = io::Error::new(io::ErrorKind::ConnectionRefused, "connection refused");
+    assert!(is_unreachable(&sqlx_core::Error::Io(refused)));
+    assert!(is_unreachable(&sqlx_core::Error::PoolTimedOut));
+
+    assert!(!is_unreachable(&sqlx_core::Error::RowNotFound));
+    assert!(!is_unreachable(&sqlx_core::Error::Protocol(
+        "syntax error".into()
+    )));
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn test_with_search_path() {
+    use sqlx_postgres::PgConnectOptions;
+
+    let url = with_search_path(
+        "postgres://localhost/sqlx?options=-c%20geqo%3Doff",
+        "app, public",
+    )
+    .unwrap();
+    let options: PgConnectOptions = url.parse().unwrap();
+    assert_eq!(
+        options.get_options(),
+        Some("-c geqo=off -c search_path=app,\\ public")
+    );
+
+    let err = with_search_path("sqlite::memory:", "app").unwrap_err();
+    assert!(
+        err.to_string().contains("only supported for Postgres"),
+        "{err}"
+    );
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn test_url_matcher() {
+    fn is_replica(url: &Url) -> bool {
+        url.query_pairs().any(|(key, _)| key == "replica")
+    }
+
+    let postgres = QueryDriver::new::<sqlx_postgres::Postgres>();
+    let replica = QueryDriver {
+        db_name: "Replica",
+        ..postgres
+    };
+
+    let replica = replica.with_url_matcher(is_replica);
+    let postgres = postgres.with_url_matcher(|url| !is_replica(url));
+
+    let drivers = [postgres, replica];
+
+    let primary = QueryDataSource::live("postgres://localhost/sqlx").unwrap();
+    let selected = primary.select_driver(&drivers).unwrap().unwrap();
+    assert_eq!(selected.db_name(), "PostgreSQL");
+
+    let secondary = QueryDataSource::live("postgresql://localhost/sqlx?replica=1").unwrap();
+    let selected = secondary.select_driver(&drivers).unwrap().unwrap();
+    assert_eq!(selected.db_name(), "Replica");
+
+    // the scheme must still match
+    let other = QueryDataSource::live("mysql://localhost/sqlx?replica=1").unwrap();
+    assert!(other.select_driver(&drivers).unwrap().is_none());
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn test_check_cached() {
+    use sqlx_postgres::Postgres;
+
+    let describe = |nullable: bool| -> Describe<Postgres> {
+        serde_json::from_value(serde_json::json!({
+            "columns": [],
+            "parameters": { "Left": [] },
+            "nullable": [nullable],
+        }))
+        .unwrap()
+    };
+
+    let cached = QueryData::from_describe("SELECT 1", describe(false));
+
+    assert!(check_cached(&cached, &describe(false)).is_ok());
+
+    let err = check_cached(&cached, &describe(true))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("its nullability changed"), "{err}");
+    assert!(err.contains("re-run `cargo sqlx prepare`"), "{err}");
+}
+
+#[test]
+#[cfg(feature = "sqlite")]
+fn test_describe_json() {
+    let json = describe_json(
+        "SELECT 1 AS one, NULL AS maybe",
+        "sqlite::memory:",
+        crate::FOSS_DRIVERS,
+    )
+    .unwrap();
+
+    let data: DynQueryData = serde_json::from_str(&json).unwrap();
+    assert_eq!(data.db_name, "SQLite");
+    assert_eq!(data.hash, hash_string("SELECT 1 AS one, NULL AS maybe"));
+    assert_eq!(data.describe["columns"][0]["name"], "one");
+    assert_eq!(data.describe["columns"][1]["name"], "maybe");
+
+    let err = describe_json("SELECT 1", "foo://bar", crate::FOSS_DRIVERS).unwrap_err();
+    assert!(err.to_string().contains("\"foo\""), "{err}");
+}
@@ -1,5 +1,6 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, ToTokens, TokenStreamExt};
+use syn::ext::IdentExt;
 use syn::Type;
 
 use sqlx_core::column::Column;
@@ -7,34 +8,139 @@ use sqlx_core::describe::Describe;
 
 use crate::database::DatabaseExt;
 
+use crate::query::metadata::Metadata;
 use crate::query::QueryMacroInput;
+use heck::{ToLowerCamelCase, ToSnakeCase, ToUpperCamelCase};
+use sqlx_core::config::macros::{self, QueryConfig, RenameAll};
 use sqlx_core::type_checking::TypeChecking;
+use sqlx_core::type_info::TypeInfo;
 use std::fmt::{self, Display, Formatter};
 use syn::parse::{Parse, ParseStream};
 use syn::Token;
 
 pub struct RustColumn {
     pub(super) ident: Ident,
+    /// The column name without any override, before `rename_all`.
+    ///
+    /// This is what the column lists in `sqlx.toml` and the hint comments are matched against.
+    pub(super) name: String,
     pub(super) var_name: Ident,
     pub(super) type_: ColumnType,
+    /// The type set with a `-- sqlx:type=T` comment, which the decoded value is cast to.
+    pub(super) override_type: Option<OverrideType>,
+}
+
+impl RustColumn {
+    /// Whether the column is listed in `names`, e.g. `macros.redacted_columns`.
+    pub(super) fn is_listed_in(&self, names: &[String]) -> bool {
+        names.iter().any(|name| *name == self.name)
+    }
+
+    /// The type of the column's field in the record.
+    pub(super) fn field_type(&self) -> TokenStream {
+        match &self.override_type {
+            Some(override_type) => override_type.rust_type(),
+            None => self.type_.to_token_stream(),
+        }
+    }
+}
+
+pub(super) struct OverrideType {
+    type_: Type,
+    nullable: bool,
+}
+
+impl OverrideType {
+    fn rust_type(&self) -> TokenStream {
+        let type_ = &self.type_;
+
+        if self.nullable {
+            quote! { ::std::option::Option<#type_> }
+        } else {
+            type_.to_token_stream()
+        }
+    }
+
+    /// Cast the value decoded into `var_name` with `as`.
+    fn cast(&self, var_name: &Ident) -> TokenStream {
+        let type_ = &self.type_;
+
+        if self.nullable {
+            quote! { #var_name.map(|value| value as #type_) }
+        } else {
+            quote! { #var_name as #type_ }
+        }
+    }
+}
+
+/// A `-- sqlx:type=T` comment, following the name of the column it's for on the same line.
+struct TypeHint {
+    column: String,
+    type_: Type,
+}
+
+/// Parse the `-- sqlx:type=T` comments in `sql`.
+fn type_hints(sql: &str) -> crate::Result<Vec<TypeHint>> {
+    sql.lines()
+        .filter_map(|line| {
+            let (before, comment) = line.split_once("--")?;
+            let type_ = comment.trim().strip_prefix("sqlx:type=")?;
+            Some((before, type_))
+        })
+        .map(|(before, type_)| {
+            let before = before.trim_end().trim_end_matches(',').trim_end();
+
+            // the column's name or alias is the last word before the comment, maybe quoted
+            let name = match before.chars().last() {
+                Some(quote @ ('"' | '`')) => {
+                    let quoted = &before[..before.len() - 1];
+                    quoted.rfind(quote).map(|i| &quoted[i + 1..])
+                }
+                _ => before
+                    .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .map_or(Some(before), |i| Some(&before[i + 1..])),
+            };
+
+            // leave out the overrides in the name, e.g. `id!` or `id: i64`
+            let column = name
+                .and_then(|name| name.split([':', '!', '?']).next())
+                .filter(|column| !column.is_empty())
+                .ok_or_else(|| {
+                    format!("`-- sqlx:type={type_}` must follow a column name on the same line")
+                })?;
+
+            let type_ = syn::parse_str(type_.trim()).map_err(|e| {
+                format!(
+                    "`-- sqlx:type={type_}` for column {column:?} is not a valid Rust type: {e}"
+                )
+            })?;
+
+            Ok(TypeHint {
+                column: column.to_string(),
+                type_,
+            })
+        })
+        .collect()
 }
 
 pub(super) enum ColumnType {
     Exact(TokenStream),
     Wildcard,
     OptWildcard,
+    /// A type that isn't known to match the column, so it's checked when decoding.
+    Checked(TokenStream),
 }
 
 impl ColumnType {
     pub(super) fn is_wildcard(&self) -> bool {
-        !matches!(self, ColumnType::Exact(_))
+        matches!(self, ColumnType::Wildcard | ColumnType::OptWildcard)
     }
 }
 
 impl ToTokens for ColumnType {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         tokens.append_all(match &self {
-            ColumnType::Exact(type_) => type_.clone().into_iter(),
+            ColumnType::Exact(type_) | ColumnType::Checked(type_) => type_.clone().into_iter(),
             ColumnType::Wildcard => quote! { _ }.into_iter(),
             ColumnType::OptWildcard => quote! { ::std::option::Option<_> }.into_iter(),
         })
@@ -49,6 +155,7 @@ struct DisplayColumn<'a> {
 
 struct ColumnDecl {
     ident: Ident,
+    name: String,
     r#override: ColumnOverride,
 }
 
@@ -70,31 +177,188 @@ enum ColumnTypeOverride {
     None,
 }
 
+/// Nullability overrides for all of a query's columns, from `/* sqlx:... */` comments
+/// at the start of the query.
+#[derive(Debug, Default, PartialEq)]
+pub struct NullabilityHint {
+    /// Treat every column as non-null, from `/* sqlx:not-null */`.
+    not_null: bool,
+    /// Treat these columns as nullable, from `/* sqlx:null=col1,col2 */`.
+    nullable: Vec<String>,
+}
+
+impl NullabilityHint {
+    /// Parse the hint comments at the start of `sql`, returning them and the rest of the query.
+    ///
+    /// Only the leading comments starting with `sqlx:` are hints; scanning stops at the first
+    /// other token, so the rest of the query is left as written.
+    pub(super) fn strip(sql: &str) -> Result<(Self, &str), String> {
+        let mut hint = Self::default();
+        let mut rest = sql;
+
+        while let Some(comment) = rest.trim_start().strip_prefix("/*") {
+            let Some(end) = comment.find("*/") else {
+                break;
+            };
+
+            let Some(directive) = comment[..end].trim().strip_prefix("sqlx:") else {
+                break;
+            };
+
+            if directive == "not-null" {
+                hint.not_null = true;
+            } else if let Some(columns) = directive.strip_prefix("null=") {
+                hint.nullable.extend(
+                    columns
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|column| !column.is_empty())
+                        .map(String::from),
+                );
+            } else {
+                return Err(format!(
+                    "unknown hint `/* sqlx:{directive} */`; \
+                     expected `/* sqlx:not-null */` or `/* sqlx:null=col1,col2 */`"
+                ));
+            }
+
+            rest = comment[end + 2..].trim_start();
+        }
+
+        Ok((hint, rest))
+    }
+
+    fn nullability(&self, column: &str) -> ColumnNullabilityOverride {
+        if self.nullable.iter().any(|c| c == column) {
+            ColumnNullabilityOverride::Nullable
+        } else if self.not_null {
+            ColumnNullabilityOverride::NonNull
+        } else {
+            ColumnNullabilityOverride::None
+        }
+    }
+}
+
 impl Display for DisplayColumn<'_> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "column #{} ({:?})", self.idx + 1, self.name)
     }
 }
 
-pub fn columns_to_rust<DB: DatabaseExt>(describe: &Describe<DB>) -> crate::Result<Vec<RustColumn>> {
-    (0..describe.columns().len())
-        .map(|i| column_to_rust(describe, i))
-        .collect::<crate::Result<Vec<_>>>()
+/// Set when the query could only be described by the fallback in
+/// [`DatabaseExt::describe_fallback_blocking()`].
+pub struct DescribeFallback<'a> {
+    /// Why the full describe failed.
+    pub reason: &'a str,
+    /// The Rust type to use for columns whose SQL type could not be mapped.
+    pub column_type: Option<&'a str>,
 }
 
-fn column_to_rust<DB: DatabaseExt>(describe: &Describe<DB>, i: usize) -> crate::Result<RustColumn> {
+/// The query being expanded, for looking up its settings in `sqlx.toml` and its hint comments.
+pub struct QueryKey<'a> {
+    /// The hash of the query, for its `query_force_nullable` entry.
+    pub hash: &'a str,
+    /// The query's `[macros.queries]` entry and its name, if it was named with a `#[name]` hint.
+    pub named: Option<(&'a str, &'a QueryConfig)>,
+    /// The `/* sqlx:... */` comments at the start of the query.
+    pub hint: &'a NullabilityHint,
+    /// The query, for its `-- sqlx:type=T` comments.
+    pub sql: &'a str,
+}
+
+pub fn columns_to_rust<DB: DatabaseExt>(
+    describe: &Describe<DB>,
+    query: &QueryKey<'_>,
+    fallback: Option<&DescribeFallback<'_>>,
+) -> crate::Result<Vec<RustColumn>> {
+    let type_hints = type_hints(query.sql)?;
+
+    let columns = (0..describe.columns().len())
+        .map(|i| column_to_rust(describe, i, query, &type_hints, fallback))
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    if let Some(hint) = type_hints.iter().find(|hint| {
+        !describe
+            .columns()
+            .iter()
+            .any(|column| column_name(column) == hint.column)
+    }) {
+        return Err(format!(
+            "`-- sqlx:type=...` refers to column {:?}, which the query doesn't return",
+            hint.column
+        )
+        .into());
+    }
+
+    if let Some(column) = query
+        .hint
+        .nullable
+        .iter()
+        .find(|column| !columns.iter().any(|c| c.name == **column))
+    {
+        return Err(format!(
+            "`/* sqlx:null=... */` refers to column {column:?}, which the query doesn't return"
+        )
+        .into());
+    }
+
+    if let Some((name, named)) = query.named {
+        let unknown = named
+            .nullable
+            .iter()
+            .chain(&named.non_null)
+            .chain(named.column_types.keys())
+            .find(|column| !columns.iter().any(|c| c.name == **column));
+
+        if let Some(column) = unknown {
+            return Err(format!(
+                "`[macros.queries.{name}]` in `sqlx.toml` refers to column {column:?}, \
+                 which the query doesn't return"
+            )
+            .into());
+        }
+    }
+
+    Ok(columns)
+}
+
+fn column_to_rust<DB: DatabaseExt>(
+    describe: &Describe<DB>,
+    i: usize,
+    query: &QueryKey<'_>,
+    type_hints: &[TypeHint],
+    fallback: Option<&DescribeFallback<'_>>,
+) -> crate::Result<RustColumn> {
     let column = &describe.columns()[i];
 
+    let rename_all = Metadata::get()
+        .config
+        .map_or(RenameAll::Verbatim, |config| config.rename_all);
+
     // add raw prefix to all identifiers
-    let decl = ColumnDecl::parse(column.name())
+    let decl = ColumnDecl::parse(column.name(), rename_all)
         .map_err(|e| format!("column name {:?} is invalid: {}", column.name(), e))?;
 
-    let ColumnOverride { nullability, type_ } = decl.r#override;
+    let ColumnOverride { nullability, type_ } = match query.named {
+        Some((name, named)) => named_override(name, named, &decl.name, decl.r#override)?,
+        None => decl.r#override,
+    };
+
+    // Overrides on the column itself or in `sqlx.toml` win over the query's hint comments.
+    let nullability = match nullability {
+        ColumnNullabilityOverride::None => query.hint.nullability(&decl.name),
+        nullability => nullability,
+    };
 
     let nullable = match nullability {
         ColumnNullabilityOverride::NonNull => false,
         ColumnNullabilityOverride::Nullable => true,
-        ColumnNullabilityOverride::None => describe.nullable(i).unwrap_or(true),
+        ColumnNullabilityOverride::None => {
+            describe.nullable(i).unwrap_or(true)
+                || Metadata::get().config.map_or(false, |config| {
+                    is_forced_nullable(config, &decl.name, query.hash)
+                })
+        }
     };
     let type_ = match (type_, nullable) {
         (ColumnTypeOverride::Exact(type_), false) => ColumnType::Exact(type_.to_token_stream()),
@@ -106,7 +370,10 @@ fn column_to_rust<DB: DatabaseExt>(describe: &Describe<DB>, i: usize) -> crate::
         (ColumnTypeOverride::Wildcard, true) => ColumnType::OptWildcard,
 
         (ColumnTypeOverride::None, _) => {
-            let type_ = get_column_type::<DB>(i, column);
+            let type_ = match configured_type_override(column)? {
+                Some(type_) => type_.to_token_stream(),
+                None => get_column_type::<DB>(i, column, fallback),
+            };
             if !nullable {
                 ColumnType::Exact(type_)
             } else {
@@ -115,29 +382,145 @@ fn column_to_rust<DB: DatabaseExt>(describe: &Describe<DB>, i: usize) -> crate::
         }
     };
 
+    let override_type = type_hints
+        .iter()
+        .find(|hint| hint.column == column_name(column))
+        .map(|hint| OverrideType {
+            type_: hint.type_.clone(),
+            nullable,
+        });
+
+    if override_type.is_some() && type_.is_wildcard() {
+        return Err(format!(
+            "`-- sqlx:type=...` can't be combined with the wildcard override for column {:?}",
+            column_name(column)
+        )
+        .into());
+    }
+
     Ok(RustColumn {
         // prefix the variable name we use in `quote_query_as!()` so it doesn't conflict
         // https://github.com/launchbadge/sqlx/issues/1322
         var_name: quote::format_ident!("sqlx_query_as_{}", decl.ident),
         ident: decl.ident,
+        name: decl.name,
         type_,
+        override_type,
     })
 }
 
+/// The column's name without the overrides in it, e.g. `id` for `id!: i64`.
+fn column_name<C: Column>(column: &C) -> &str {
+    let name = column.name();
+    name.split([':', '!', '?']).next().unwrap_or(name)
+}
+
 pub fn quote_query_as<DB: DatabaseExt>(
     input: &QueryMacroInput,
     out_ty: &Type,
     bind_args: &Ident,
     columns: &[RustColumn],
+) -> TokenStream {
+    let ident = columns.iter().map(|col| &col.ident);
+    let var_name = columns.iter().map(|col| &col.var_name);
+
+    // with `#[default]`, fields that aren't columns come from `Default`
+    let rest = input
+        .default_fields
+        .map(|_| quote!(..::core::default::Default::default()));
+
+    quote_query_map::<DB>(
+        input,
+        bind_args,
+        columns,
+        quote!(#out_ty { #(#ident: #var_name,)* #rest }),
+    )
+}
+
+/// Map each row to a tuple of the columns, in order.
+///
+/// The tuple's types must be known, so wildcard overrides and the `_unchecked!()` variants,
+/// which leave the field types of `query_as!()` to inference, are not supported.
+pub fn quote_query_tuple<DB: DatabaseExt>(
+    input: &QueryMacroInput,
+    bind_args: &Ident,
+    columns: &[RustColumn],
+) -> crate::Result<TokenStream> {
+    if !input.checked {
+        return Err(syn::Error::new(
+            input.src_span,
+            "tuples are only supported by the checked macros; use `query_as_unchecked!()` instead",
+        )
+        .into());
+    }
+
+    if let Some(column) = columns
+        .iter()
+        .find(|column| matches!(column.type_, ColumnType::Wildcard | ColumnType::OptWildcard))
+    {
+        return Err(syn::Error::new(
+            input.src_span,
+            format!(
+                "wildcard override for column `{}` can't be inferred in a tuple; \
+                 use `query_as!()` with a struct instead",
+                column.ident.unraw()
+            ),
+        )
+        .into());
+    }
+
+    let var_name = columns.iter().map(|col| &col.var_name);
+    let type_ = columns.iter().map(RustColumn::field_type);
+
+    // the type annotation is the target of the `.into()` for the columns with exact types
+    Ok(quote_query_map::<DB>(
+        input,
+        bind_args,
+        columns,
+        quote! {{
+            let tuple: (#(#type_,)*) = (#(#var_name,)*);
+            tuple
+        }},
+    ))
+}
+
+/// Decode the columns of each row into their `var_name`s and evaluate `construct`.
+fn quote_query_map<DB: DatabaseExt>(
+    input: &QueryMacroInput,
+    bind_args: &Ident,
+    columns: &[RustColumn],
+    construct: TokenStream,
 ) -> TokenStream {
     let instantiations = columns.iter().enumerate().map(
         |(
             i,
             RustColumn {
-                var_name, type_, ..
+                var_name,
+                type_,
+                override_type,
+                ..
             },
         )| {
+            if let Some(override_type) = override_type {
+                let cast = override_type.cast(var_name);
+
+                // decode the inferred type so the cast knows what it's casting from
+                let get = match type_ {
+                    ColumnType::Checked(_) => quote!(try_get),
+                    _ => quote!(try_get_unchecked),
+                };
+
+                return quote! {
+                    let #var_name: #type_ = row.#get::<#type_, _>(#i)?;
+                    let #var_name = #cast;
+                };
+            }
+
             match (input.checked, type_) {
+                // the type comes from `mock_describe` so it needs the runtime check either way
+                (_, ColumnType::Checked(type_)) => {
+                    quote! ( let #var_name = row.try_get::<#type_, _>(#i)?; )
+                }
                 // we guarantee the type is valid so we can skip the runtime check
                 (true, ColumnType::Exact(type_)) => quote! {
                     // binding to a `let` avoids confusing errors about
@@ -156,18 +539,20 @@ pub fn quote_query_as<DB: DatabaseExt>(
         },
     );
 
-    let ident = columns.iter().map(|col| &col.ident);
-    let var_name = columns.iter().map(|col| &col.var_name);
-
     let db_path = DB::db_path();
     let row_path = DB::row_path();
 
     // if this query came from a file, use `include_str!()` to tell the compiler where it came from
-    let sql = if let Some(ref path) = &input.file_path {
-        quote::quote_spanned! { input.src_span => include_str!(#path) }
-    } else {
-        let sql = &input.sql;
-        quote! { #sql }
+    let sql = &input.sql;
+    let sql = match &input.file_path {
+        Some(path) if !input.rewritten => {
+            quote::quote_spanned! { input.src_span => include_str!(#path) }
+        }
+        // still include the file so the compiler knows to rebuild when it changes
+        Some(path) => quote::quote_spanned! { input.src_span =>
+            { const _: &str = include_str!(#path); #sql }
+        },
+        None => quote! { #sql },
     };
 
     quote! {
@@ -176,7 +561,7 @@ pub fn quote_query_as<DB: DatabaseExt>(
 
             #(#instantiations)*
 
-            ::std::result::Result::Ok(#out_ty { #(#ident: #var_name),* })
+            ::std::result::Result::Ok(#construct)
         })
     }
 }
@@ -185,6 +570,8 @@ pub fn quote_query_scalar<DB: DatabaseExt>(
     input: &QueryMacroInput,
     bind_args: &Ident,
     describe: &Describe<DB>,
+    query: &QueryKey<'_>,
+    fallback: Option<&DescribeFallback<'_>>,
 ) -> crate::Result<TokenStream> {
     let columns = describe.columns();
 
@@ -196,12 +583,35 @@ pub fn quote_query_scalar<DB: DatabaseExt>(
         .into());
     }
 
-    // attempt to parse a column override, otherwise fall back to the inferred type of the column
-    let ty = if let Ok(rust_col) = column_to_rust(describe, 0) {
+    let type_hints = type_hints(query.sql)?;
+
+    let rename_all = Metadata::get()
+        .config
+        .map_or(RenameAll::Verbatim, |config| config.rename_all);
+
+    // Use the column's overrides if its name parses, otherwise fall back to the inferred type of
+    // the column. Either way a nullable column, e.g. `MAX(x)`, is decoded as `Option<T>`.
+    let ty = if ColumnDecl::parse(columns[0].name(), rename_all).is_ok() {
+        let rust_col = column_to_rust(describe, 0, query, &type_hints, fallback)?;
+
+        if rust_col.override_type.is_some() {
+            // the cast needs a closure to happen in
+            let var_name = &rust_col.var_name;
+
+            return Ok(quote_query_map::<DB>(
+                input,
+                bind_args,
+                std::slice::from_ref(&rust_col),
+                quote!(#var_name),
+            ));
+        }
+
         rust_col.type_.to_token_stream()
     } else if input.checked {
-        let ty = get_column_type::<DB>(0, &columns[0]);
-        if describe.nullable(0).unwrap_or(true) {
+        let ty = get_column_type::<DB>(0, &columns[0], fallback);
+
+        // without a name, only `/* sqlx:not-null */` can override the inferred nullability
+        if !query.hint.not_null && describe.nullable(0).unwrap_or(true) {
             quote! { ::std::option::Option<#ty> }
         } else {
             ty
@@ -218,12 +628,98 @@ pub fn quote_query_scalar<DB: DatabaseExt>(
     })
 }
 
-fn get_column_type<DB: DatabaseExt>(i: usize, column: &DB::Column) -> TokenStream {
+/// Whether the column is listed in `force_nullable`, or in the `query_force_nullable` entry
+/// for the query.
+fn is_forced_nullable(config: &macros::Config, column: &str, query_hash: &str) -> bool {
+    let listed = |columns: &Vec<String>| columns.iter().any(|c| c == column);
+
+    listed(&config.force_nullable)
+        || config
+            .query_force_nullable
+            .get(query_hash)
+            .map_or(false, listed)
+}
+
+/// Fill in the parts of the column's override that weren't written in the query from its
+/// named query's `[macros.queries]` entry.
+fn named_override(
+    name: &str,
+    named: &QueryConfig,
+    column: &str,
+    r#override: ColumnOverride,
+) -> crate::Result<ColumnOverride> {
+    let listed = |columns: &[String]| columns.iter().any(|c| c == column);
+
+    let nullability = match r#override.nullability {
+        ColumnNullabilityOverride::None if listed(&named.nullable) => {
+            ColumnNullabilityOverride::Nullable
+        }
+        ColumnNullabilityOverride::None if listed(&named.non_null) => {
+            ColumnNullabilityOverride::NonNull
+        }
+        nullability => nullability,
+    };
+
+    let type_ = match (r#override.type_, named.column_types.get(column)) {
+        (ColumnTypeOverride::None, Some(rust_type)) => match syn::parse_str(rust_type) {
+            Ok(Type::Infer(_)) => ColumnTypeOverride::Wildcard,
+            Ok(ty) => ColumnTypeOverride::Exact(ty),
+            Err(e) => {
+                return Err(format!(
+                    "`[macros.queries.{name}]` type {rust_type:?} for column {column:?} \
+                     is not a valid Rust type: {e}"
+                )
+                .into())
+            }
+        },
+        (type_, _) => type_,
+    };
+
+    Ok(ColumnOverride { nullability, type_ })
+}
+
+/// The Rust type set for the column's SQL type in `macros.type_overrides`, if any.
+fn configured_type_override<C: Column>(column: &C) -> crate::Result<Option<Type>> {
+    let Some(config) = Metadata::get().config else {
+        return Ok(None);
+    };
+
+    let type_name = column.type_info().name();
+
+    let Some(rust_type) = config
+        .type_overrides
+        .iter()
+        .find(|(sql_type, _)| sql_type.eq_ignore_ascii_case(type_name))
+        .map(|(_, rust_type)| rust_type)
+    else {
+        return Ok(None);
+    };
+
+    syn::parse_str(rust_type).map(Some).map_err(|e| {
+        format!("`type_overrides` value {rust_type:?} for SQL type {type_name} is not a valid Rust type: {e}").into()
+    })
+}
+
+fn get_column_type<DB: DatabaseExt>(
+    i: usize,
+    column: &DB::Column,
+    fallback: Option<&DescribeFallback<'_>>,
+) -> TokenStream {
     let type_info = column.type_info();
 
     <DB as TypeChecking>::return_type_for_id(type_info).map_or_else(
         || {
-            let message =
+            if let Some(column_type) = fallback.and_then(|fallback| fallback.column_type) {
+                return column_type.parse().unwrap_or_else(|_| {
+                    syn::Error::new(
+                        Span::call_site(),
+                        format!("`describe_fallback_type` {column_type:?} is not a valid Rust type"),
+                    )
+                    .to_compile_error()
+                });
+            }
+
+            let mut message =
                 if let Some(feature_gate) = <DB as TypeChecking>::get_feature_gate(type_info) {
                     format!(
                         "optional sqlx feature `{feat}` required for type {ty} of {col}",
@@ -244,6 +740,17 @@ fn get_column_type<DB: DatabaseExt>(i: usize, column: &DB::Column) -> TokenStrea
                         }
                     )
                 };
+
+            if let Some(fallback) = fallback {
+                message = format!(
+                    "{message}\n\
+                     note: the query was only partially described because describing it failed: {}\n\
+                     help: set `describe_fallback_type` in the `[macros]` section of `sqlx.toml` \
+                     to use a fallback type for such columns",
+                    fallback.reason
+                );
+            }
+
             syn::Error::new(Span::call_site(), message).to_compile_error()
         },
         |t| t.parse().unwrap(),
@@ -251,20 +758,25 @@ fn get_column_type<DB: DatabaseExt>(i: usize, column: &DB::Column) -> TokenStrea
 }
 
 impl ColumnDecl {
-    fn parse(col_name: &str) -> crate::Result<Self> {
+    fn parse(col_name: &str, rename_all: RenameAll) -> crate::Result<Self> {
         // find the end of the identifier because we want to use our own logic to parse it
         // if we tried to feed this into `syn::parse_str()` we might get an un-great error
         // for some kinds of invalid identifiers
-        let (ident, remainder) = if let Some(i) = col_name.find(&[':', '!', '?'][..]) {
-            let (ident, remainder) = col_name.split_at(i);
+        let (name, remainder) = match col_name.find(&[':', '!', '?'][..]) {
+            Some(i) => col_name.split_at(i),
+            None => (col_name, ""),
+        };
 
-            (parse_ident(ident)?, remainder)
-        } else {
-            (parse_ident(col_name)?, "")
+        let ident = match rename_all {
+            RenameAll::Verbatim => parse_ident(name)?,
+            RenameAll::SnakeCase => parse_renamed_ident(name, name.to_snake_case())?,
+            RenameAll::CamelCase => parse_renamed_ident(name, name.to_lower_camel_case())?,
+            RenameAll::PascalCase => parse_renamed_ident(name, name.to_upper_camel_case())?,
         };
 
         Ok(ColumnDecl {
             ident,
+            name: name.to_owned(),
             r#override: if !remainder.is_empty() {
                 syn::parse_str(remainder)?
             } else {
@@ -311,7 +823,13 @@ impl Parse for ColumnOverride {
     }
 }
 
-fn parse_ident(name: &str) -> crate::Result<Ident> {
+fn parse_renamed_ident(name: &str, renamed: String) -> crate::Result<Ident> {
+    parse_ident(&renamed).map_err(|_| {
+        format!("{renamed:?}, from renaming column {name:?} with `rename_all`, is not a valid Rust identifier").into()
+    })
+}
+
+pub(super) fn parse_ident(name: &str) -> crate::Result<Ident> {
     // workaround for the following issue (it's semi-fixed but still spits out extra diagnostics)
     // https://github.com/dtolnay/syn/issues/749#issuecomment-575451318
 
@@ -328,3 +846,144 @@ fn parse_ident(name: &str) -> crate::Result<Ident> {
 
     Err(format!("{name:?} is not a valid Rust identifier").into())
 }
+
+#[test]
+fn test_column_decl_rename_all() {
+    let ident = |name, rename_all| {
+        ColumnDecl::parse(name, rename_all)
+            .map(|decl| decl.ident.unraw().to_string())
+            .map_err(|e| e.to_string())
+    };
+
+    assert_eq!(ident("userId", RenameAll::Verbatim).unwrap(), "userId");
+    assert_eq!(ident("userId!", RenameAll::SnakeCase).unwrap(), "user_id");
+    assert_eq!(
+        ident("user_id: i64", RenameAll::CamelCase).unwrap(),
+        "userId"
+    );
+    assert_eq!(ident("user_id?", RenameAll::PascalCase).unwrap(), "UserId");
+
+    // `heck` drops the leading underscores, leaving a leading digit
+    let err = ident("_1st_place", RenameAll::CamelCase).unwrap_err();
+    assert!(err.contains("\"1stPlace\""), "{err}");
+    assert!(err.contains("column \"_1st_place\""), "{err}");
+}
+
+#[test]
+fn test_listed_columns_match_original_name() {
+    let decl = ColumnDecl::parse("password_hash!", RenameAll::CamelCase).unwrap();
+
+    let column = RustColumn {
+        var_name: quote::format_ident!("sqlx_query_as_{}", decl.ident),
+        ident: decl.ident,
+        name: decl.name,
+        type_: ColumnType::Wildcard,
+        override_type: None,
+    };
+
+    assert_eq!(column.ident.unraw(), "passwordHash");
+
+    // renaming the field doesn't stop a configured column from being redacted
+    assert!(column.is_listed_in(&["password_hash".to_owned()]));
+    assert!(!column.is_listed_in(&["passwordHash".to_owned()]));
+}
+
+#[test]
+fn test_nullability_lists_match_original_name() {
+    let decl = ColumnDecl::parse("user_id", RenameAll::CamelCase).unwrap();
+    assert_eq!(decl.ident.unraw(), "userId");
+
+    let config = macros::Config {
+        rename_all: RenameAll::CamelCase,
+        force_nullable: vec!["user_id".to_owned()],
+        query_force_nullable: [("0000".to_owned(), vec!["user_id".to_owned()])].into(),
+        ..Default::default()
+    };
+
+    assert!(is_forced_nullable(&config, &decl.name, "1111"));
+    assert!(!is_forced_nullable(&config, "userId", "1111"));
+
+    let config = macros::Config {
+        query_force_nullable: config.query_force_nullable,
+        ..Default::default()
+    };
+
+    assert!(is_forced_nullable(&config, &decl.name, "0000"));
+    assert!(!is_forced_nullable(&config, &decl.name, "1111"));
+
+    let named = QueryConfig {
+        non_null: vec!["user_id".to_owned()],
+        column_types: [("user_id".to_owned(), "i64".to_owned())].into(),
+        ..Default::default()
+    };
+
+    let r#override = named_override("user", &named, &decl.name, decl.r#override).unwrap();
+    assert!(matches!(
+        r#override.nullability,
+        ColumnNullabilityOverride::NonNull
+    ));
+    assert!(matches!(r#override.type_, ColumnTypeOverride::Exact(_)));
+
+    let (hint, _) = NullabilityHint::strip("/* sqlx:null=user_id */ SELECT 1 AS user_id").unwrap();
+    assert!(matches!(
+        hint.nullability(&decl.name),
+        ColumnNullabilityOverride::Nullable
+    ));
+}
+
+#[test]
+fn test_nullability_hint_strip() {
+    let (hint, sql) = NullabilityHint::strip("SELECT 1 /* sqlx:not-null */").unwrap();
+    assert_eq!(hint, NullabilityHint::default());
+    assert_eq!(sql, "SELECT 1 /* sqlx:not-null */");
+
+    let (hint, sql) =
+        NullabilityHint::strip("\n  /* sqlx:not-null */\n/* sqlx:null= a, b */ SELECT a, b")
+            .unwrap();
+    assert!(hint.not_null);
+    assert_eq!(hint.nullable, ["a", "b"]);
+    assert_eq!(sql, "SELECT a, b");
+
+    // other comments end the hints
+    let (hint, sql) =
+        NullabilityHint::strip("/* the query */ /* sqlx:not-null */ SELECT 1").unwrap();
+    assert_eq!(hint, NullabilityHint::default());
+    assert_eq!(sql, "/* the query */ /* sqlx:not-null */ SELECT 1");
+
+    let err = NullabilityHint::strip("/* sqlx:non-null */ SELECT 1").unwrap_err();
+    assert!(err.contains("`/* sqlx:non-null */`"), "{err}");
+}
+
+#[test]
+fn test_type_hints() {
+    let hints = type_hints(
+        "SELECT id, -- sqlx:type=u32\n\
+         count(*) AS \"total!\" -- sqlx:type=std::primitive::u64\n\
+         name -- just a comment\n\
+         FROM users",
+    )
+    .unwrap()
+    .into_iter()
+    .map(|hint| (hint.column, hint.type_.to_token_stream().to_string()))
+    .collect::<Vec<_>>();
+
+    assert_eq!(
+        hints,
+        [
+            ("id".to_string(), "u32".to_string()),
+            ("total".to_string(), "std :: primitive :: u64".to_string()),
+        ]
+    );
+
+    let Err(err) = type_hints("SELECT\n-- sqlx:type=u32\nid") else {
+        panic!("expected an error");
+    };
+    let err = err.to_string();
+    assert!(err.contains("must follow a column name"), "{err}");
+
+    let Err(err) = type_hints("SELECT id -- sqlx:type=u 32") else {
+        panic!("expected an error");
+    };
+    let err = err.to_string();
+    assert!(err.contains("not a valid Rust type"), "{err}");
+}
@@ -0,0 +1,316 @@
+//! Scanning the bind parameters in a query without a database, for `macros.assert_param_count`
+//! and `macros.enable_named_params`.
+
+use std::cmp;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PlaceholderStyle {
+    /// `$1`, `$2`, ...; the count is the highest index.
+    Dollar,
+    /// `?`, counted one by one.
+    Question,
+    /// `?` and `?NNN`; the count is the highest index assigned.
+    ///
+    /// Named parameters (`:name`, `@name`, `$name`) are also valid but not counted.
+    Sqlite,
+}
+
+impl PlaceholderStyle {
+    pub fn for_database(db_name: &str) -> Option<Self> {
+        match db_name {
+            "PostgreSQL" => Some(Self::Dollar),
+            "MySQL" => Some(Self::Question),
+            "SQLite" => Some(Self::Sqlite),
+            _ => None,
+        }
+    }
+}
+
+/// Count the bind parameters in `sql`, skipping over string literals, quoted identifiers and
+/// comments.
+///
+/// Returns `None` if the count can't be determined, e.g. if the query uses named parameters.
+pub fn count_placeholders(sql: &str, style: PlaceholderStyle) -> Option<usize> {
+    let bytes = sql.as_bytes();
+    let mut count = 0;
+    let mut i = 0;
+
+    // MySQL always treats backslashes in strings as escapes; Postgres only in `E'...'` strings.
+    let backslash_escapes = style == PlaceholderStyle::Question;
+
+    while i < bytes.len() {
+        let prev_is_ident = i > 0 && is_ident_byte(bytes[i - 1]);
+
+        match bytes[i] {
+            b'\'' => {
+                let escape_string = i > 0
+                    && bytes[i - 1].eq_ignore_ascii_case(&b'e')
+                    && !(i > 1 && is_ident_byte(bytes[i - 2]));
+                i = skip_quoted(bytes, i, b'\'', backslash_escapes || escape_string);
+            }
+            b'"' => i = skip_quoted(bytes, i, b'"', backslash_escapes),
+            b'`' if style != PlaceholderStyle::Dollar => i = skip_quoted(bytes, i, b'`', false),
+            b'[' if style == PlaceholderStyle::Sqlite => i = skip_until(bytes, i + 1, b"]"),
+            b'-' if bytes.get(i + 1) == Some(&b'-') => i = skip_until(bytes, i + 2, b"\n"),
+            b'#' if style == PlaceholderStyle::Question => i = skip_until(bytes, i + 1, b"\n"),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => i = skip_until(bytes, i + 2, b"*/"),
+            b'$' if style == PlaceholderStyle::Dollar && !prev_is_ident => {
+                let digits = count_digits(&bytes[i + 1..]);
+
+                if digits > 0 {
+                    count = cmp::max(count, sql[i + 1..i + 1 + digits].parse().ok()?);
+                    i += 1 + digits;
+                } else if let Some(tag_len) = dollar_quote_tag_len(&bytes[i..]) {
+                    // `$tag$ ... $tag$`
+                    let tag = &bytes[i..i + tag_len];
+                    i = skip_until(bytes, i + tag_len, tag);
+                } else {
+                    i += 1;
+                }
+            }
+            b'?' if style == PlaceholderStyle::Question => {
+                count += 1;
+                i += 1;
+            }
+            b'?' if style == PlaceholderStyle::Sqlite => {
+                let digits = count_digits(&bytes[i + 1..]);
+
+                if digits > 0 {
+                    count = cmp::max(count, sql[i + 1..i + 1 + digits].parse().ok()?);
+                } else {
+                    count += 1;
+                }
+
+                i += 1 + digits;
+            }
+            b':' | b'@' | b'$'
+                if style == PlaceholderStyle::Sqlite
+                    && !prev_is_ident
+                    && bytes.get(i + 1).map_or(false, |&b| is_ident_byte(b)) =>
+            {
+                return None;
+            }
+            _ => i += 1,
+        }
+    }
+
+    Some(count)
+}
+
+/// Number the `$name` placeholders in `sql` as `$1`, `$2`, ... in order of their first use,
+/// skipping over string literals, quoted identifiers and comments the same as
+/// [`count_placeholders()`] with [`PlaceholderStyle::Dollar`].
+///
+/// Returns the rewritten query and the names in the order they were numbered. Errors if the
+/// query also has numbered placeholders.
+pub fn number_named_placeholders(sql: &str) -> Result<(String, Vec<String>), String> {
+    let bytes = sql.as_bytes();
+    let mut numbered = String::with_capacity(sql.len());
+    let mut names: Vec<String> = Vec::new();
+    // `sql[..copied]` has been copied to `numbered`
+    let mut copied = 0;
+    let mut numbered_placeholder = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let prev_is_ident = i > 0 && is_ident_byte(bytes[i - 1]);
+
+        match bytes[i] {
+            b'\'' => {
+                let escape_string = i > 0
+                    && bytes[i - 1].eq_ignore_ascii_case(&b'e')
+                    && !(i > 1 && is_ident_byte(bytes[i - 2]));
+                i = skip_quoted(bytes, i, b'\'', escape_string);
+            }
+            b'"' => i = skip_quoted(bytes, i, b'"', false),
+            b'-' if bytes.get(i + 1) == Some(&b'-') => i = skip_until(bytes, i + 2, b"\n"),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => i = skip_until(bytes, i + 2, b"*/"),
+            b'$' if !prev_is_ident => {
+                let digits = count_digits(&bytes[i + 1..]);
+
+                if digits > 0 {
+                    numbered_placeholder.get_or_insert(&sql[i..i + 1 + digits]);
+                    i += 1 + digits;
+                    continue;
+                }
+
+                if let Some(tag_len) = dollar_quote_tag_len(&bytes[i..]) {
+                    let tag = &bytes[i..i + tag_len];
+                    i = skip_until(bytes, i + tag_len, tag);
+                    continue;
+                }
+
+                let len = bytes[i + 1..]
+                    .iter()
+                    .take_while(|&&b| is_ident_byte(b))
+                    .count();
+
+                if len == 0 {
+                    i += 1;
+                    continue;
+                }
+
+                let name = &sql[i + 1..i + 1 + len];
+
+                let n = match names.iter().position(|n| n == name) {
+                    Some(pos) => pos + 1,
+                    None => {
+                        names.push(name.to_string());
+                        names.len()
+                    }
+                };
+
+                numbered.push_str(&sql[copied..i]);
+                numbered.push_str(&format!("${n}"));
+
+                i += 1 + len;
+                copied = i;
+            }
+            _ => i += 1,
+        }
+    }
+
+    if let Some(placeholder) = numbered_placeholder.filter(|_| !names.is_empty()) {
+        return Err(format!(
+            "`$name` placeholders can't be mixed with numbered placeholders like `{placeholder}`"
+        ));
+    }
+
+    numbered.push_str(&sql[copied..]);
+
+    Ok((numbered, names))
+}
+
+/// A `const` item that fails to evaluate if `placeholders != args`.
+pub fn quote_assertion(placeholders: usize, args: usize, span: Span) -> TokenStream {
+    let message =
+        format!("the query has {placeholders} bind parameter(s) but {args} argument(s) were given");
+
+    quote_spanned! { span=>
+        const _: () = ::std::assert!(#placeholders == #args, #message);
+    }
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80
+}
+
+fn count_digits(bytes: &[u8]) -> usize {
+    bytes.iter().take_while(|b| b.is_ascii_digit()).count()
+}
+
+/// The length of the `$tag$` at the start of `bytes`, if there is one.
+fn dollar_quote_tag_len(bytes: &[u8]) -> Option<usize> {
+    let tag = bytes[1..].iter().take_while(|&&b| is_ident_byte(b)).count();
+
+    (bytes.get(1 + tag) == Some(&b'$')).then_some(tag + 2)
+}
+
+/// Skip past the quoted string starting at `start`. A doubled quote is an escaped quote.
+fn skip_quoted(bytes: &[u8], start: usize, quote: u8, backslash_escapes: bool) -> usize {
+    let mut i = start + 1;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if backslash_escapes => i += 2,
+            b if b == quote && bytes.get(i + 1) == Some(&quote) => i += 2,
+            b if b == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+
+    i
+}
+
+/// Skip to just past the next occurrence of `end`, or to the end of the query.
+fn skip_until(bytes: &[u8], start: usize, end: &[u8]) -> usize {
+    bytes[cmp::min(start, bytes.len())..]
+        .windows(end.len())
+        .position(|window| window == end)
+        .map_or(bytes.len(), |pos| start + pos + end.len())
+}
+
+#[test]
+fn test_count_placeholders() {
+    use PlaceholderStyle::*;
+
+    assert_eq!(count_placeholders("SELECT 1", Dollar), Some(0));
+    assert_eq!(count_placeholders("SELECT $1, $2, $1", Dollar), Some(2));
+    assert_eq!(count_placeholders("SELECT $2", Dollar), Some(2));
+    assert_eq!(
+        count_placeholders("SELECT '$3', \"$4\", $1", Dollar),
+        Some(1)
+    );
+    assert_eq!(count_placeholders("SELECT 'it''s $2', $1", Dollar), Some(1));
+    assert_eq!(count_placeholders(r"SELECT E'\'$2', $1", Dollar), Some(1));
+    assert_eq!(count_placeholders("SELECT $$ $2 $$, $1", Dollar), Some(1));
+    assert_eq!(count_placeholders("SELECT $f$ $2 $f$, $1", Dollar), Some(1));
+    assert_eq!(
+        count_placeholders("SELECT a$1 FROM t WHERE x = $1", Dollar),
+        Some(1)
+    );
+    assert_eq!(
+        count_placeholders("SELECT $1 -- $2\n, $1 /* $3 */", Dollar),
+        Some(1)
+    );
+    assert_eq!(
+        count_placeholders("SELECT '{}'::jsonb ? 'a'", Dollar),
+        Some(0)
+    );
+
+    assert_eq!(count_placeholders("SELECT ?, ?", Question), Some(2));
+    assert_eq!(
+        count_placeholders(r"SELECT '\'?', `?`, ?", Question),
+        Some(1)
+    );
+    assert_eq!(count_placeholders("SELECT ? # ?\n", Question), Some(1));
+    assert_eq!(count_placeholders("SELECT @x := ?", Question), Some(1));
+
+    assert_eq!(count_placeholders("SELECT ?, ?", Sqlite), Some(2));
+    assert_eq!(count_placeholders("SELECT ?1, ?1", Sqlite), Some(1));
+    assert_eq!(count_placeholders("SELECT ?3, ?", Sqlite), Some(4));
+    assert_eq!(
+        count_placeholders("SELECT '?', [?], \"?\", ?", Sqlite),
+        Some(1)
+    );
+    assert_eq!(count_placeholders("SELECT :name", Sqlite), None);
+    assert_eq!(count_placeholders("SELECT ':name', ?", Sqlite), Some(1));
+}
+
+#[test]
+fn test_quote_assertion() {
+    let tokens = quote_assertion(2, 1, Span::call_site()).to_string();
+
+    assert!(tokens.contains("assert ! (2usize == 1usize"), "{tokens}");
+    assert!(
+        tokens.contains("the query has 2 bind parameter(s) but 1 argument(s) were given"),
+        "{tokens}"
+    );
+}
+
+#[test]
+fn test_number_named_placeholders() {
+    let (sql, names) = number_named_placeholders(
+        "SELECT * FROM t WHERE a = $b AND c = $a_1 OR d = $b -- $e\n AND f = '$g' AND h = $$ $i $$",
+    )
+    .unwrap();
+    assert_eq!(
+        sql,
+        "SELECT * FROM t WHERE a = $1 AND c = $2 OR d = $1 -- $e\n AND f = '$g' AND h = $$ $i $$"
+    );
+    assert_eq!(names, ["b", "a_1"]);
+
+    let (sql, names) = number_named_placeholders("SELECT $tag$ $a $tag$, a$b").unwrap();
+    assert_eq!(sql, "SELECT $tag$ $a $tag$, a$b");
+    assert!(names.is_empty());
+
+    let (sql, names) = number_named_placeholders("SELECT $1, $2").unwrap();
+    assert_eq!(sql, "SELECT $1, $2");
+    assert!(names.is_empty());
+
+    let err = number_named_placeholders("SELECT $1, $a").unwrap_err();
+    assert!(err.contains("`$1`"), "{err}");
+}
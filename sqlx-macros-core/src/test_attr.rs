@@ -147,8 +147,10 @@ fn expand_advanced(args: AttributeArgs, input: syn::ItemFn) -> crate::Result<Tok
             quote! { args.migrator(&#migrator); }
         }
         MigrationsOpt::InferredPath if !inputs.is_empty() => {
-            let migrations_path =
-                crate::common::resolve_path("./migrations", proc_macro2::Span::call_site())?;
+            let migrations_path = crate::common::resolve_path(
+                crate::migrate::default_migrations_dir()?,
+                proc_macro2::Span::call_site(),
+            )?;
 
             if migrations_path.is_dir() {
                 let migrator = crate::migrate::expand_migrator(&migrations_path)?;
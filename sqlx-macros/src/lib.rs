@@ -68,8 +68,14 @@ pub fn derive_from_row(input: TokenStream) -> TokenStream {
 pub fn migrate(input: TokenStream) -> TokenStream {
     use syn::LitStr;
 
-    let input = syn::parse_macro_input!(input as LitStr);
-    match migrate::expand_migrator_from_lit_dir(input) {
+    let input = syn::parse_macro_input!(input as Option<LitStr>);
+
+    let res = match input {
+        Some(dir) => migrate::expand_migrator_from_lit_dir(dir),
+        None => migrate::expand_migrator_from_config(),
+    };
+
+    match res {
         Ok(ts) => ts.into(),
         Err(e) => {
             if let Some(parse_err) = e.downcast_ref::<syn::Error>() {
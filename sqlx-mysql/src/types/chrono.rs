@@ -6,7 +6,7 @@ use sqlx_core::database::Database;
 
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
-use crate::error::{BoxDynError, UnexpectedNullError};
+use crate::error::{BoxDynError, DecodeError};
 use crate::protocol::text::ColumnType;
 use crate::type_info::MySqlTypeInfo;
 use crate::types::{MySqlTime, MySqlTimeSign, Type};
@@ -198,7 +198,7 @@ impl<'r> Decode<'r, MySql> for NaiveDate {
                     return Err("empty buffer".into());
                 }
 
-                decode_date(&buf[1..])?.ok_or_else(|| UnexpectedNullError.into())
+                decode_date(&buf[1..])?.ok_or_else(|| DecodeError::UnexpectedNull.into())
             }
 
             MySqlValueFormat::Text => {
@@ -263,7 +263,7 @@ impl<'r> Decode<'r, MySql> for NaiveDateTime {
                 }
 
                 let len = buf[0];
-                let date = decode_date(&buf[1..])?.ok_or(UnexpectedNullError)?;
+                let date = decode_date(&buf[1..])?.ok_or(DecodeError::UnexpectedNull)?;
 
                 let dt = if len > 4 {
                     date.and_time(decode_time(len - 4, &buf[5..])?)
@@ -128,6 +128,7 @@
 //! | [`Json<T>`]                           | JSON                                                 |
 //! | `serde_json::JsonValue`               | JSON                                                 |
 //! | `&serde_json::value::RawValue`        | JSON                                                 |
+//! | `Box<serde_json::value::RawValue>`    | JSON                                                 |
 //!
 //! # Nullable
 //!
@@ -6,7 +6,7 @@ use time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
 
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
-use crate::error::{BoxDynError, UnexpectedNullError};
+use crate::error::{BoxDynError, DecodeError};
 use crate::protocol::text::ColumnType;
 use crate::type_info::MySqlTypeInfo;
 use crate::types::{MySqlTime, MySqlTimeSign, Type};
@@ -173,7 +173,7 @@ impl<'r> Decode<'r, MySql> for Date {
                     return Err("empty buffer".into());
                 }
 
-                Ok(decode_date(&buf[1..])?.ok_or(UnexpectedNullError)?)
+                Ok(decode_date(&buf[1..])?.ok_or(DecodeError::UnexpectedNull)?)
             }
             MySqlValueFormat::Text => {
                 let s = value.as_str()?;
@@ -232,7 +232,7 @@ impl<'r> Decode<'r, MySql> for PrimitiveDateTime {
 
                 let len = buf.get_u8();
 
-                let date = decode_date(buf)?.ok_or(UnexpectedNullError)?;
+                let date = decode_date(buf)?.ok_or(DecodeError::UnexpectedNull)?;
 
                 let dt = if len > 4 {
                     date.with_time(decode_time(&buf[4..])?)
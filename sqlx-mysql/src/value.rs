@@ -4,7 +4,7 @@ use std::str::from_utf8;
 use bytes::Bytes;
 pub(crate) use sqlx_core::value::*;
 
-use crate::error::{BoxDynError, UnexpectedNullError};
+use crate::error::{BoxDynError, DecodeError};
 use crate::protocol::text::ColumnType;
 use crate::{MySql, MySqlTypeInfo};
 
@@ -40,7 +40,7 @@ impl<'r> MySqlValueRef<'r> {
     pub(crate) fn as_bytes(&self) -> Result<&'r [u8], BoxDynError> {
         match &self.value {
             Some(v) => Ok(v),
-            None => Err(UnexpectedNullError.into()),
+            None => Err(DecodeError::UnexpectedNull.into()),
         }
     }
 
@@ -4,7 +4,7 @@ use crate::executor::{Execute, Executor};
 use crate::logger::QueryLogger;
 use crate::message::{
     self, Bind, Close, CommandComplete, DataRow, MessageFormat, ParameterDescription, Parse, Query,
-    RowDescription,
+    RowDescription, TransactionStatus,
 };
 use crate::statement::PgStatementMetadata;
 use crate::types::Oid;
@@ -16,7 +16,9 @@ use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_core::Stream;
 use futures_util::{pin_mut, TryStreamExt};
+use sqlx_core::row::Row;
 use sqlx_core::Either;
+use std::time::Duration;
 use std::{borrow::Cow, sync::Arc};
 
 async fn prepare(
@@ -192,7 +194,15 @@ impl PgConnection {
         limit: u8,
         persistent: bool,
         metadata_opt: Option<Arc<PgStatementMetadata>>,
+        timeout: Option<Duration>,
     ) -> Result<impl Stream<Item = Result<Either<PgQueryResult, PgRow>, Error>> + 'e, Error> {
+        // in case the stream of a previous query with a timeout was dropped before it finished
+        self.restore_statement_timeout().await?;
+
+        if let Some(timeout) = timeout {
+            self.set_statement_timeout(timeout).await?;
+        }
+
         let mut logger = QueryLogger::new(query, self.log_settings.clone());
 
         // before we continue, wait until we are "ready" to accept more queries
@@ -267,7 +277,16 @@ impl PgConnection {
 
         Ok(try_stream! {
             loop {
-                let message = self.stream.recv().await?;
+                let message = match self.stream.recv().await {
+                    Ok(message) => message,
+                    Err(error) => {
+                        // the error is more useful than one from restoring the timeout, which
+                        // would also be returned by the next query
+                        let _ = self.restore_statement_timeout().await;
+
+                        return Err(timed_out(error, timeout));
+                    }
+                };
 
                 match message.format {
                     MessageFormat::BindComplete
@@ -346,9 +365,59 @@ impl PgConnection {
                 }
             }
 
+            self.restore_statement_timeout().await?;
+
             Ok(())
         })
     }
+
+    /// Set `statement_timeout` for a query with `Query::timeout()`, remembering the previous
+    /// value for [`Self::restore_statement_timeout()`].
+    async fn set_statement_timeout(&mut self, timeout: Duration) -> Result<(), Error> {
+        // the setting is an `int` of milliseconds, where `0` disables the timeout
+        let millis = timeout.as_millis().clamp(1, i32::MAX as u128);
+
+        let sql = format!("SHOW statement_timeout; SET statement_timeout = {millis}");
+
+        let previous: String = Executor::fetch_one(&mut *self, sql.as_str())
+            .await?
+            .try_get(0)?;
+
+        self.statement_timeout_to_restore = Some(previous);
+
+        Ok(())
+    }
+
+    /// Put back the `statement_timeout` replaced by [`Self::set_statement_timeout()`], if any.
+    async fn restore_statement_timeout(&mut self) -> Result<(), Error> {
+        let Some(previous) = self.statement_timeout_to_restore.take() else {
+            return Ok(());
+        };
+
+        self.wait_until_ready().await?;
+
+        // in a failed transaction block, rolling back undoes the `SET` instead
+        if matches!(self.transaction_status, TransactionStatus::Error) {
+            return Ok(());
+        }
+
+        let sql = format!("SET statement_timeout = '{}'", previous.replace('\'', "''"));
+
+        Executor::execute(&mut *self, sql.as_str()).await?;
+
+        Ok(())
+    }
+}
+
+/// Report the statement being cancelled by `statement_timeout` as [`Error::QueryTimedOut`].
+fn timed_out(error: Error, timeout: Option<Duration>) -> Error {
+    match (error, timeout) {
+        // query_canceled
+        (Error::Database(e), Some(timeout)) if e.code().as_deref() == Some("57014") => {
+            Error::QueryTimedOut(timeout)
+        }
+        (error, _) => error,
+    }
 }
 
 impl<'c> Executor<'c> for &'c mut PgConnection {
@@ -370,10 +439,11 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
         let arguments = query.take_arguments().map_err(Error::Encode);
         let persistent = query.persistent();
+        let timeout = query.timeout();
 
         Box::pin(try_stream! {
             let arguments = arguments?;
-            let s = self.run(sql, arguments, 0, persistent, metadata).await?;
+            let s = self.run(sql, arguments, 0, persistent, metadata, timeout).await?;
             pin_mut!(s);
 
             while let Some(v) = s.try_next().await? {
@@ -397,10 +467,13 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
         let arguments = query.take_arguments().map_err(Error::Encode);
         let persistent = query.persistent();
+        let timeout = query.timeout();
 
         Box::pin(async move {
             let arguments = arguments?;
-            let s = self.run(sql, arguments, 1, persistent, metadata).await?;
+            let s = self
+                .run(sql, arguments, 1, persistent, metadata, timeout)
+                .await?;
             pin_mut!(s);
 
             // With deferred constraints we need to check all responses as we
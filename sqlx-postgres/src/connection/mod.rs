@@ -64,6 +64,9 @@ pub struct PgConnection {
     transaction_status: TransactionStatus,
     pub(crate) transaction_depth: usize,
 
+    // the `statement_timeout` to put back after a query with `Query::timeout()`
+    statement_timeout_to_restore: Option<String>,
+
     log_settings: LogSettings,
 }
 
@@ -35,6 +35,8 @@ impl Database for Postgres {
     const NAME: &'static str = "PostgreSQL";
 
     const URL_SCHEMES: &'static [&'static str] = &["postgres", "postgresql"];
+
+    const SUPPORTS_STATEMENT_TIMEOUT: bool = true;
 }
 
 impl HasStatementCache for Postgres {}
@@ -193,7 +193,12 @@ where
         // This could be done more efficiently by refactoring the Vec decoding below so that it can
         // be used for arrays and Vec.
         let vec: Vec<T> = Decode::decode(value)?;
-        let array: [T; N] = vec.try_into().map_err(|_| "wrong number of elements")?;
+        let array: [T; N] = vec.try_into().map_err(|vec: Vec<T>| {
+            format!(
+                "wrong number of elements: expected an array of {N}, got {}",
+                vec.len()
+            )
+        })?;
         Ok(array)
     }
 }
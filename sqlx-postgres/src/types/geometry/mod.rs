@@ -0,0 +1,97 @@
+//! Postgres geometric types.
+//!
+//! All of these are made up of points, which are sent as two `FLOAT8`s, `x` then `y`.
+//! The parsers for points here are shared between the types.
+
+mod point;
+
+pub use point::PgPoint;
+
+use crate::error::BoxDynError;
+
+/// The size of a point in the binary format: two big-endian `FLOAT8`s.
+const POINT_BYTES: usize = 16;
+
+/// Parse a point in the text format, `(x,y)`.
+///
+/// Surrounding whitespace and the parentheses are optional, as they are for input to Postgres.
+/// Coordinates are anything `f64::from_str()` accepts, which covers the output of Postgres,
+/// including exponents (`1e+20`), `Infinity`, `-Infinity` and `NaN`.
+fn parse_point(s: &str) -> Result<(f64, f64), BoxDynError> {
+    let s = s.trim();
+
+    let coords = s
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .unwrap_or(s);
+
+    let (x, y) = coords
+        .split_once(',')
+        .ok_or_else(|| format!("expected a point as `(x,y)`, got {s:?}"))?;
+
+    let parse_coord = |coord: &str| {
+        coord
+            .trim()
+            .parse::<f64>()
+            .map_err(|e| format!("invalid coordinate {coord:?} in point {s:?}: {e}"))
+    };
+
+    Ok((parse_coord(x)?, parse_coord(y)?))
+}
+
+/// Read the point at `offset` in the binary format.
+fn read_point(bytes: &[u8], offset: usize) -> Result<(f64, f64), BoxDynError> {
+    let point = bytes.get(offset..offset + POINT_BYTES).ok_or_else(|| {
+        format!(
+            "expected at least {} bytes, got {}",
+            offset + POINT_BYTES,
+            bytes.len()
+        )
+    })?;
+
+    let (x, y) = point.split_at(POINT_BYTES / 2);
+
+    Ok((
+        f64::from_be_bytes(x.try_into()?),
+        f64::from_be_bytes(y.try_into()?),
+    ))
+}
+
+fn write_point(buf: &mut Vec<u8>, (x, y): (f64, f64)) {
+    buf.extend_from_slice(&x.to_be_bytes());
+    buf.extend_from_slice(&y.to_be_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_point, read_point, write_point};
+
+    #[test]
+    fn test_parse_point() {
+        assert_eq!(parse_point("(1,2)").unwrap(), (1.0, 2.0));
+        assert_eq!(parse_point(" ( 1.5 , -2.25 ) ").unwrap(), (1.5, -2.25));
+        assert_eq!(parse_point("-1,-2").unwrap(), (-1.0, -2.0));
+        assert_eq!(parse_point("(1e+20,-3.5e-07)").unwrap(), (1e20, -3.5e-7));
+
+        let (x, y) = parse_point("(Infinity,NaN)").unwrap();
+        assert_eq!(x, f64::INFINITY);
+        assert!(y.is_nan());
+
+        assert!(parse_point("(1)").is_err());
+        assert!(parse_point("(1,2,3)").is_err());
+        assert!(parse_point("(a,2)").is_err());
+        assert!(parse_point("").is_err());
+    }
+
+    #[test]
+    fn test_point_bytes_round_trip() {
+        let mut buf = Vec::new();
+        write_point(&mut buf, (-1.5, 2e100));
+        write_point(&mut buf, (0.0, -0.0));
+
+        assert_eq!(buf.len(), 32);
+        assert_eq!(read_point(&buf, 0).unwrap(), (-1.5, 2e100));
+        assert_eq!(read_point(&buf, 16).unwrap(), (0.0, -0.0));
+        assert!(read_point(&buf, 24).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+use std::str::FromStr;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+use super::{parse_point, read_point, write_point, POINT_BYTES};
+
+/// A point on a plane, the Postgres `POINT` type.
+///
+/// In the binary format this is two big-endian `FLOAT8`s, `x` then `y`.
+/// In the text format it is `(x,y)`; see [`FromStr`] for what is accepted.
+///
+/// See also: <https://www.postgresql.org/docs/current/datatype-geometric.html#DATATYPE-GEOMETRIC-POINTS>
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct PgPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Type<Postgres> for PgPoint {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::POINT
+    }
+}
+
+impl PgHasArrayType for PgPoint {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::POINT_ARRAY
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for PgPoint {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Text => Ok(value.as_str()?.parse()?),
+            PgValueFormat::Binary => {
+                let bytes = value.as_bytes()?;
+
+                if bytes.len() != POINT_BYTES {
+                    return Err(format!(
+                        "expected {POINT_BYTES} bytes for POINT, got {}",
+                        bytes.len()
+                    )
+                    .into());
+                }
+
+                let (x, y) = read_point(bytes, 0)?;
+                Ok(PgPoint { x, y })
+            }
+        }
+    }
+}
+
+impl Encode<'_, Postgres> for PgPoint {
+    fn produces(&self) -> Option<PgTypeInfo> {
+        Some(PgTypeInfo::POINT)
+    }
+
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        write_point(buf, (self.x, self.y));
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        POINT_BYTES
+    }
+}
+
+/// Parses `(x,y)`, where either coordinate may be negative or use an exponent (`-1.5e-10`).
+///
+/// Surrounding whitespace and the parentheses are optional.
+impl FromStr for PgPoint {
+    type Err = BoxDynError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = parse_point(s)?;
+        Ok(PgPoint { x, y })
+    }
+}
@@ -69,6 +69,7 @@ impl Encode<'_, Postgres> for PgInterval {
 
 // We then implement Encode + Type for std Duration, chrono Duration, and time Duration
 // This is to enable ease-of-use for encoding when its simple
+// std Duration also implements Decode, for intervals without months
 
 impl Type<Postgres> for std::time::Duration {
     fn type_info() -> PgTypeInfo {
@@ -92,6 +93,33 @@ impl Encode<'_, Postgres> for std::time::Duration {
     }
 }
 
+impl<'r> Decode<'r, Postgres> for std::time::Duration {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let interval = PgInterval::decode(value)?;
+
+        if interval.months != 0 {
+            return Err(format!(
+                "`std::time::Duration` cannot decode an INTERVAL with months, \
+                 which have no fixed length; got {} months",
+                interval.months
+            )
+            .into());
+        }
+
+        // like `extract(epoch from ..)`, count a day as 24 hours
+        let micros = i64::from(interval.days)
+            .checked_mul(86_400_000_000)
+            .and_then(|days| days.checked_add(interval.microseconds))
+            .ok_or("Overflow has occurred for `std::time::Duration`")?;
+
+        let micros = u64::try_from(micros).map_err(|_| {
+            format!("`std::time::Duration` can only decode non-negative INTERVAL values; got {micros} microseconds")
+        })?;
+
+        Ok(std::time::Duration::from_micros(micros))
+    }
+}
+
 impl TryFrom<std::time::Duration> for PgInterval {
     type Error = BoxDynError;
 
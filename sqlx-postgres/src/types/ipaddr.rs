@@ -1,4 +1,4 @@
-use std::net::IpAddr;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
 use ipnetwork::IpNetwork;
 
@@ -17,7 +17,7 @@ where
     }
 
     fn compatible(ty: &PgTypeInfo) -> bool {
-        IpNetwork::compatible(ty)
+        IpNetwork::compatible(ty) || <&str as Type<Postgres>>::compatible(ty)
     }
 }
 
@@ -49,6 +49,11 @@ where
     IpNetwork: Decode<'db, Postgres>,
 {
     fn decode(value: PgValueRef<'db>) -> Result<Self, BoxDynError> {
+        // for compatibility with the text-based impls for other databases
+        if <&str as Type<Postgres>>::compatible(&value.type_info) {
+            return Ok(<&str as Decode<Postgres>>::decode(value)?.parse()?);
+        }
+
         let ipnetwork = IpNetwork::decode(value)?;
 
         if ipnetwork.is_ipv4() && ipnetwork.prefix() != 32
@@ -60,3 +65,81 @@ where
         Ok(ipnetwork.ip())
     }
 }
+
+impl Type<Postgres> for Ipv4Addr {
+    fn type_info() -> PgTypeInfo {
+        IpAddr::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        IpAddr::compatible(ty)
+    }
+}
+
+impl PgHasArrayType for Ipv4Addr {
+    fn array_type_info() -> PgTypeInfo {
+        <IpAddr as PgHasArrayType>::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        <IpAddr as PgHasArrayType>::array_compatible(ty)
+    }
+}
+
+impl<'db> Encode<'db, Postgres> for Ipv4Addr {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        IpAddr::from(*self).encode_by_ref(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        IpAddr::from(*self).size_hint()
+    }
+}
+
+impl<'db> Decode<'db, Postgres> for Ipv4Addr {
+    fn decode(value: PgValueRef<'db>) -> Result<Self, BoxDynError> {
+        match IpAddr::decode(value)? {
+            IpAddr::V4(ip) => Ok(ip),
+            ip => Err(format!("expected an IPv4 address, got {ip}").into()),
+        }
+    }
+}
+
+impl Type<Postgres> for Ipv6Addr {
+    fn type_info() -> PgTypeInfo {
+        IpAddr::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        IpAddr::compatible(ty)
+    }
+}
+
+impl PgHasArrayType for Ipv6Addr {
+    fn array_type_info() -> PgTypeInfo {
+        <IpAddr as PgHasArrayType>::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        <IpAddr as PgHasArrayType>::array_compatible(ty)
+    }
+}
+
+impl<'db> Encode<'db, Postgres> for Ipv6Addr {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        IpAddr::from(*self).encode_by_ref(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        IpAddr::from(*self).size_hint()
+    }
+}
+
+impl<'db> Decode<'db, Postgres> for Ipv6Addr {
+    fn decode(value: PgValueRef<'db>) -> Result<Self, BoxDynError> {
+        match IpAddr::decode(value)? {
+            IpAddr::V6(ip) => Ok(ip),
+            ip => Err(format!("expected an IPv6 address, got {ip}").into()),
+        }
+    }
+}
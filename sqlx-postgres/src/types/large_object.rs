@@ -0,0 +1,140 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::{Oid, Type};
+use crate::{PgArgumentBuffer, PgConnection, PgTypeInfo, PgValueRef, Postgres};
+
+// `INV_READ` from `libpq/libpq-fs.h`
+const INV_READ: i32 = 0x40000;
+
+/// A handle to a Postgres [large object], decoded from the `OID` referencing it.
+///
+/// Decoding this type doesn't read the object itself. Instead, [`open()`][Self::open] it on a
+/// connection to read the contents in chunks, so a large object never needs to be in memory
+/// all at once.
+///
+/// Both `OID` columns and the `lo` type from the [`lo` extension] are supported.
+///
+/// ### Note: Transactions
+/// Large object descriptors only last until the end of the transaction they were opened in,
+/// so the handle must be opened and read within a transaction on the same connection
+/// (e.g. using `&mut *tx` for a `Transaction<Postgres>`). Outside of a transaction, reads fail
+/// with an "invalid large-object descriptor" error.
+///
+/// ### Example
+/// ```rust,no_run
+/// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+/// use sqlx::postgres::types::PgLargeObject;
+///
+/// let mut tx = pool.begin().await?;
+///
+/// let blob: PgLargeObject = sqlx::query_scalar("SELECT data FROM media WHERE id = $1")
+///     .bind(1_i64)
+///     .fetch_one(&mut *tx)
+///     .await?;
+///
+/// let mut reader = blob.open(&mut *tx).await?;
+///
+/// while let Some(chunk) = reader.read_chunk(64 * 1024).await? {
+///     // forward `chunk` somewhere
+/// }
+///
+/// reader.close().await?;
+/// tx.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [large object]: https://www.postgresql.org/docs/current/largeobjects.html
+/// [`lo` extension]: https://www.postgresql.org/docs/current/lo.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PgLargeObject {
+    oid: Oid,
+}
+
+impl PgLargeObject {
+    /// A handle to the large object with the given `OID`.
+    pub fn new(oid: Oid) -> Self {
+        Self { oid }
+    }
+
+    /// The `OID` of the large object.
+    pub fn oid(&self) -> Oid {
+        self.oid
+    }
+
+    /// Open the large object for reading with `lo_open()`.
+    ///
+    /// A connection-like type is required to execute the call. Allowed types include
+    /// `PgConnection`, `PoolConnection<Postgres>` and `Transaction<Postgres>`, as well as
+    /// mutable references to any of these. See the note on transactions on [`PgLargeObject`].
+    pub async fn open<C: AsMut<PgConnection>>(
+        &self,
+        mut conn: C,
+    ) -> crate::Result<PgLargeObjectReader<C>> {
+        let fd: i32 = crate::query_scalar::query_scalar("SELECT lo_open($1, $2)")
+            .bind(self.oid)
+            .bind(INV_READ)
+            .fetch_one(conn.as_mut())
+            .await?;
+
+        Ok(PgLargeObjectReader { conn, fd })
+    }
+}
+
+/// An open large object, returned by [`PgLargeObject::open()`].
+///
+/// The descriptor is closed at the end of the transaction if [`close()`][Self::close] is not
+/// called first.
+#[derive(Debug)]
+pub struct PgLargeObjectReader<C: AsMut<PgConnection>> {
+    conn: C,
+    fd: i32,
+}
+
+impl<C: AsMut<PgConnection>> PgLargeObjectReader<C> {
+    /// Read up to `max_len` bytes from the current position with `loread()`.
+    ///
+    /// Returns `None` at the end of the object.
+    pub async fn read_chunk(&mut self, max_len: i32) -> crate::Result<Option<Vec<u8>>> {
+        let chunk: Vec<u8> = crate::query_scalar::query_scalar("SELECT loread($1, $2)")
+            .bind(self.fd)
+            .bind(max_len)
+            .fetch_one(self.conn.as_mut())
+            .await?;
+
+        Ok((!chunk.is_empty()).then_some(chunk))
+    }
+
+    /// Close the descriptor with `lo_close()`, returning the connection.
+    pub async fn close(mut self) -> crate::Result<C> {
+        crate::query::query("SELECT lo_close($1)")
+            .bind(self.fd)
+            .execute(self.conn.as_mut())
+            .await?;
+
+        Ok(self.conn)
+    }
+}
+
+impl Type<Postgres> for PgLargeObject {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::OID
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::OID || *ty == PgTypeInfo::with_name("lo")
+    }
+}
+
+impl Encode<'_, Postgres> for PgLargeObject {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        self.oid.encode_by_ref(buf)
+    }
+}
+
+impl Decode<'_, Postgres> for PgLargeObject {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        Oid::decode(value).map(Self::new)
+    }
+}
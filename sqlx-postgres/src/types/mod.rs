@@ -15,18 +15,24 @@
 //! | `&[u8]`, `Vec<u8>`                    | BYTEA                                                |
 //! | `()`                                  | VOID                                                 |
 //! | [`PgInterval`]                        | INTERVAL                                             |
+//! | [`Duration`][std::time::Duration]     | INTERVAL<sup>2</sup>                                 |
 //! | [`PgRange<T>`](PgRange)               | INT8RANGE, INT4RANGE, TSRANGE, TSTZRANGE, DATERANGE, NUMRANGE |
 //! | [`PgMoney`]                           | MONEY                                                |
 //! | [`PgLTree`]                           | LTREE                                                |
 //! | [`PgLQuery`]                          | LQUERY                                               |
 //! | [`PgCiText`]                          | CITEXT<sup>1</sup>                                   |
 //! | [`PgCube`]                            | CUBE                                                 |
+//! | [`PgPoint`]                           | POINT                                                |
 //! | [`PgHstore`]                          | HSTORE                                               |
+//! | [`PgLargeObject`]                     | OID, LO                                              |
 //!
 //! <sup>1</sup> SQLx generally considers `CITEXT` to be compatible with `String`, `&str`, etc.,
 //! but this wrapper type is available for edge cases, such as `CITEXT[]` which Postgres
 //! does not consider to be compatible with `TEXT[]`.
 //!
+//! <sup>2</sup> Decoding counts a day as 24 hours, and returns an error if the `INTERVAL` has
+//! a months component or is negative.
+//!
 //! ### [`bigdecimal`](https://crates.io/crates/bigdecimal)
 //! Requires the `bigdecimal` Cargo feature flag.
 //!
@@ -85,11 +91,17 @@
 //! | Rust type                             | Postgres type(s)                                     |
 //! |---------------------------------------|------------------------------------------------------|
 //! | `ipnetwork::IpNetwork`                | INET, CIDR                                           |
-//! | `std::net::IpAddr`                    | INET, CIDR                                           |
+//! | `std::net::IpAddr`                    | INET, CIDR, TEXT (decode only)                       |
+//! | `std::net::Ipv4Addr`                  | INET, CIDR, TEXT (decode only)                       |
+//! | `std::net::Ipv6Addr`                  | INET, CIDR, TEXT (decode only)                       |
 //!
 //! Note that because `IpAddr` does not support network prefixes, it is an error to attempt to decode
 //! an `IpAddr` from a `INET` or `CIDR` value with a network prefix smaller than the address' full width:
 //! `/32` for IPv4 addresses and `/128` for IPv6 addresses.
+//! Likewise, it is an error to decode an `Ipv4Addr` from an IPv6 address or vice versa.
+//!
+//! The `std::net` types also decode from `TEXT` for compatibility with MySQL and SQLite,
+//! which store them as text and don't require a feature flag.
 //!
 //! `IpNetwork` does not have this limitation.
 //!
@@ -118,6 +130,7 @@
 //! | [`Json<T>`]                           | JSON, JSONB                                          |
 //! | `serde_json::Value`                   | JSON, JSONB                                          |
 //! | `&serde_json::value::RawValue`        | JSON, JSONB                                          |
+//! | `Box<serde_json::value::RawValue>`    | JSON, JSONB                                          |
 //!
 //! `Value` and `RawValue` from `serde_json` can be used for unstructured JSON data with
 //! Postgres.
@@ -191,6 +204,7 @@ mod float;
 mod hstore;
 mod int;
 mod interval;
+mod large_object;
 mod lquery;
 mod ltree;
 // Not behind a Cargo feature because we require JSON in the driver implementation.
@@ -212,6 +226,8 @@ mod bigdecimal;
 
 mod cube;
 
+mod geometry;
+
 #[cfg(any(feature = "bigdecimal", feature = "rust_decimal"))]
 mod numeric;
 
@@ -242,8 +258,10 @@ mod bit_vec;
 pub use array::PgHasArrayType;
 pub use citext::PgCiText;
 pub use cube::PgCube;
+pub use geometry::PgPoint;
 pub use hstore::PgHstore;
 pub use interval::PgInterval;
+pub use large_object::{PgLargeObject, PgLargeObjectReader};
 pub use lquery::PgLQuery;
 pub use lquery::PgLQueryLevel;
 pub use lquery::PgLQueryVariant;
@@ -1,4 +1,4 @@
-use crate::error::{BoxDynError, UnexpectedNullError};
+use crate::error::{BoxDynError, DecodeError};
 use crate::{PgTypeInfo, Postgres};
 use sqlx_core::bytes::{Buf, Bytes};
 use std::borrow::Cow;
@@ -58,7 +58,7 @@ impl<'r> PgValueRef<'r> {
     pub fn as_bytes(&self) -> Result<&'r [u8], BoxDynError> {
         match &self.value {
             Some(v) => Ok(v),
-            None => Err(UnexpectedNullError.into()),
+            None => Err(DecodeError::UnexpectedNull.into()),
         }
     }
 
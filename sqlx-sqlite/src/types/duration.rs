@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+// Stored as the number of microseconds, the same precision as Postgres' `INTERVAL`.
+
+impl Type<Sqlite> for Duration {
+    fn type_info() -> SqliteTypeInfo {
+        <i64 as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <i64 as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for Duration {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<SqliteArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        if self.subsec_nanos() % 1000 != 0 {
+            return Err("`std::time::Duration` is stored as microseconds, got nanoseconds".into());
+        }
+
+        args.push(SqliteArgumentValue::Int64(self.as_micros().try_into()?));
+
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for Duration {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        let micros = <i64 as Decode<Sqlite>>::decode(value)?;
+
+        let micros = u64::try_from(micros).map_err(|_| {
+            format!("`std::time::Duration` can only decode non-negative values; got {micros}")
+        })?;
+
+        Ok(Duration::from_micros(micros))
+    }
+}
@@ -0,0 +1,101 @@
+use std::borrow::Cow;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+impl Type<Sqlite> for Ipv4Addr {
+    fn type_info() -> SqliteTypeInfo {
+        <&str as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <&str as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for Ipv4Addr {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<SqliteArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        args.push(SqliteArgumentValue::Text(Cow::Owned(self.to_string())));
+
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Sqlite> for Ipv4Addr {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        // delegate to the &str type to decode from SQLite
+        let text = <&str as Decode<Sqlite>>::decode(value)?;
+
+        // parse a Ipv4Addr from the text
+        text.parse().map_err(Into::into)
+    }
+}
+
+impl Type<Sqlite> for Ipv6Addr {
+    fn type_info() -> SqliteTypeInfo {
+        <&str as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <&str as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for Ipv6Addr {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<SqliteArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        args.push(SqliteArgumentValue::Text(Cow::Owned(self.to_string())));
+
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Sqlite> for Ipv6Addr {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        // delegate to the &str type to decode from SQLite
+        let text = <&str as Decode<Sqlite>>::decode(value)?;
+
+        // parse a Ipv6Addr from the text
+        text.parse().map_err(Into::into)
+    }
+}
+
+impl Type<Sqlite> for IpAddr {
+    fn type_info() -> SqliteTypeInfo {
+        <&str as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <&str as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for IpAddr {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<SqliteArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        args.push(SqliteArgumentValue::Text(Cow::Owned(self.to_string())));
+
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Sqlite> for IpAddr {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        // delegate to the &str type to decode from SQLite
+        let text = <&str as Decode<Sqlite>>::decode(value)?;
+
+        // parse a IpAddr from the text
+        text.parse().map_err(Into::into)
+    }
+}
@@ -17,6 +17,10 @@
 //! | `f64`                                 | REAL                                                 |
 //! | `&str`, [`String`]                    | TEXT                                                 |
 //! | `&[u8]`, `Vec<u8>`                    | BLOB                                                 |
+//! | `IpAddr`                              | TEXT                                                 |
+//! | `Ipv4Addr`                            | TEXT                                                 |
+//! | `Ipv6Addr`                            | TEXT                                                 |
+//! | [`Duration`][std::time::Duration]     | INTEGER (microseconds; see note)                     |
 //!
 //! #### Note: Unsigned Integers
 //! Decoding of unsigned integer types simply performs a checked conversion
@@ -36,6 +40,11 @@
 //! Bit-casting `u64` to `i64`, or storing it as `REAL`, `BLOB` or `TEXT`,
 //! would change the semantics of the value in SQL and so violates the principle of least surprise.
 //!
+//! #### Note: `Duration`
+//! `std::time::Duration` is stored as a number of microseconds, the same precision as an
+//! `INTERVAL` in Postgres. Encoding a duration with sub-microsecond precision is an error,
+//! as is decoding a negative number.
+//!
 //! ### [`chrono`](https://crates.io/crates/chrono)
 //!
 //! Requires the `chrono` Cargo feature flag.
@@ -101,6 +110,7 @@
 //! | [`Json<T>`]                           | TEXT                                                 |
 //! | `serde_json::JsonValue`               | TEXT                                                 |
 //! | `&serde_json::value::RawValue`        | TEXT                                                 |
+//! | `Box<serde_json::value::RawValue>`    | TEXT                                                 |
 //!
 //! # Nullable
 //!
@@ -167,7 +177,9 @@ mod bool;
 mod bytes;
 #[cfg(feature = "chrono")]
 mod chrono;
+mod duration;
 mod float;
+mod inet;
 mod int;
 #[cfg(feature = "json")]
 mod json;
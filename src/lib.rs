@@ -3,6 +3,7 @@
 
 pub use sqlx_core::acquire::Acquire;
 pub use sqlx_core::arguments::{Arguments, IntoArguments};
+pub use sqlx_core::codec::Codec;
 pub use sqlx_core::column::Column;
 pub use sqlx_core::column::ColumnIndex;
 pub use sqlx_core::connection::{ConnectOptions, Connection};
@@ -34,6 +35,13 @@ pub use sqlx_core::error::{self, Error, Result};
 #[cfg(feature = "migrate")]
 pub use sqlx_core::migrate;
 
+#[cfg(any(feature = "config-migrate", feature = "config-pool"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "config-migrate", feature = "config-pool")))
+)]
+pub use sqlx_core::config;
+
 #[cfg(feature = "mysql")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mysql")))]
 #[doc(inline)]
@@ -55,6 +55,10 @@
 ///   server with the schema that the query string will be checked against.
 ///   All variants of `query!()` use [dotenv]<sup>1</sup> so this can be in a `.env` file instead.
 ///
+///     * The variable to read can be changed with the `database_url_var` key in the `[macros]`
+///       section of a `sqlx.toml` file in the crate root, which also accepts a list of variables
+///       to try in order.
+///
 ///     * Or, `.sqlx` must exist at the workspace root. See [Offline Mode](#offline-mode-requires-the-offline-feature)
 ///       below.
 ///
@@ -110,6 +114,48 @@
 /// * Postgres: `$N` where `N` is the 1-based positional argument index
 /// * MySQL/SQLite: `?` which matches arguments in order that it appears in the query
 ///
+/// ##### Parameter Structs (Postgres and SQLite)
+/// With `enable_params_struct = true` and `enable_named_params = true` (see below) in the
+/// `[macros]` section of `sqlx.toml`, the arguments may instead be given as a single struct value,
+/// `params = <expr>`. Each `$name` placeholder is bound to the field of the same name, which is
+/// type-checked like any other argument. The struct is your own, so it can be constructed ahead
+/// of time and passed around before the query is executed:
+///
+/// ```rust,ignore
+/// struct AccountKey {
+///     id: i32,
+///     name: Option<String>,
+/// }
+///
+/// let key = AccountKey { id: 1, name: Some("Herp Derpinson".to_string()) };
+///
+/// let account = sqlx::query!(
+///         "select * from accounts where id = $id and name = $name",
+///         params = &key
+///     )
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
+/// ##### Named Parameters (Postgres and SQLite)
+/// With `enable_named_params = true` in the `[macros]` section of `sqlx.toml`, bind parameters
+/// may be written `$name` instead of `$N`. Each is bound to the argument that is the variable
+/// `name`, so the arguments can be given in any order and a value used more than once is only
+/// passed once:
+///
+/// ```rust,ignore
+/// let accounts = sqlx::query!(
+///         "select * from accounts where owner = $user_id or created_by = $user_id limit $limit",
+///         user_id,
+///         limit
+///     )
+///     .fetch_all(&mut conn)
+///     .await?;
+/// ```
+///
+/// The macro numbers the placeholders before the query is checked, so the database never sees
+/// the names. A query can't use both `$name` and `$N` placeholders.
+///
 /// ## Nullability: Bind Parameters
 /// For a given expected type `T`, both `T` and `Option<T>` are allowed (as well as either
 /// behind references). `Option::None` will be bound as `NULL`, so if binding a type behind `Option`
@@ -247,6 +293,32 @@
 /// (as the exact value of bind parameters can change the query plan)
 /// and the definitions of any relevant tables (or sufficiently anonymized equivalents).
 ///
+/// ##### Hint Comments
+/// To override the nullability of many columns at once, e.g. for a query on a view that Postgres
+/// reports as all nullable, start the query with a `/* sqlx:not-null */` comment to treat every
+/// column as non-null, and a `/* sqlx:null=col1,col2 */` comment to treat the listed columns as
+/// nullable. The hint comments are stripped from the query before it is sent to the database.
+///
+/// The `!` and `?` overrides on a column take precedence over the hint comments:
+///
+/// ```rust,ignore
+/// # async fn main() {
+/// # let mut conn = panic!();
+/// let record = sqlx::query!(
+///     r#"
+///     /* sqlx:not-null */
+///     /* sqlx:null=email */
+///     select id, name, email, nickname as "nickname?" from users_view
+///     "#
+/// )
+/// .fetch_one(&mut conn)
+/// .await?;
+///
+/// // `record.id: i32`, `record.name: String`,
+/// // `record.email: Option<String>`, `record.nickname: Option<String>`
+/// # }
+/// ```
+///
 /// ##### Force a Different/Custom Type
 /// Selecting a column `foo as "foo: T"` (Postgres / SQLite) or `` foo as `foo: T` `` (MySQL)
 /// overrides the inferred type which is useful when selecting user-defined custom types
@@ -275,6 +347,30 @@
 /// # }
 /// ```
 ///
+/// ##### Cast with a Comment
+/// A `-- sqlx:type=T` comment after a column's name or alias on the same line decodes the
+/// column as its inferred type and then casts it to `T` with `as`, keeping the inferred
+/// nullability. As with `as` in Rust, this is for converting between primitive types; use
+/// `foo: T` for other types.
+///
+/// ```rust,ignore
+/// # async fn main() {
+/// # let mut conn = panic!();
+/// let record = sqlx::query!(
+///     r#"
+///     select id, -- sqlx:type=u32
+///            name
+///     from users
+///     "#
+/// )
+/// .fetch_one(&mut conn)
+/// .await?;
+///
+/// // `id` is `INT4 NOT NULL`, decoded as `i32` and then cast
+/// let id: u32 = record.id;
+/// # }
+/// ```
+///
 /// ##### Overrides cheatsheet
 ///
 /// | Syntax    | Nullability     | Type       |
@@ -303,8 +399,83 @@
 /// project and your database schema itself, run
 /// `cargo install sqlx-cli && cargo sqlx prepare --check` in your Continuous Integration script.
 ///
+/// Alternatively, build with `SQLX_OFFLINE=true`, `SQLX_OFFLINE_CHECK=true` and `DATABASE_URL`
+/// set: each query is then still expanded from the `.sqlx` directory, but also described by the
+/// database, and compilation fails if the cached data is out of date.
+///
+/// Online builds with `SQLX_OFFLINE_DIR` set write the query data to that directory as they go,
+/// which is how `cargo sqlx prepare` collects it. Set `SQLX_NO_SAVE=true` to describe the queries
+/// against the database without writing anything, e.g. to keep `.sqlx` untouched in CI.
+///
+/// With `SQLX_OFFLINE_FALLBACK=true` (or `offline_fallback = true` in the `[macros]` section of
+/// `sqlx.toml`), online builds expand queries from the `.sqlx` directory if the database can't
+/// be reached, printing a warning for each one, instead of failing to compile.
+///
 /// See [the README for `sqlx-cli`](https://crates.io/crates/sqlx-cli) for more information.
 ///
+/// ## Timeout Hints
+/// With `enable_timeout_hints = true` in the `[macros]` section of `sqlx.toml`, the query string
+/// may be preceded by a `#[timeout = "..."]` hint, which calls
+/// [`Query::timeout()`][crate::query::Query::timeout] on the generated query. The duration is a
+/// whole number followed by `ms`, `s` or `m`:
+///
+/// ```rust,ignore
+/// let account = sqlx::query!(#[timeout = "200ms"] "select * from accounts where id = ?", 1i32)
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
+/// Executing the query then fails with [`Error::QueryTimedOut`][crate::Error::QueryTimedOut]
+/// if it takes longer than that. In Postgres this sets `statement_timeout` for the query, so the
+/// server cancels the statement. Other drivers enforce the timeout on the client, so the database
+/// server keeps running the statement. Set a server-side limit as well (e.g. `max_execution_time`
+/// in MySQL) if the statement itself should be cancelled.
+///
+/// ## Driver Hints
+/// To check queries against more than one database in the same crate, map a URL scheme of each
+/// driver to the environment variable holding its database URL in the
+/// `[macros.driver_url_vars]` section of `sqlx.toml`, and precede each query string with a
+/// `#[driver = "..."]` hint naming the scheme:
+///
+/// ```toml
+/// [macros.driver_url_vars]
+/// postgres = "PG_DATABASE_URL"
+/// sqlite = "SQLITE_DATABASE_URL"
+/// ```
+///
+/// ```rust,ignore
+/// let account = sqlx::query!(#[driver = "sqlite"] "select * from accounts where id = ?", 1i32)
+///     .fetch_one(&mut sqlite_conn)
+///     .await?;
+/// ```
+///
+/// The query is then described using the URL in that variable and expanded only for that
+/// driver. Queries without a hint read the URL as usual.
+///
+/// ## Named Queries
+/// Overrides for the columns of a query may be kept in `sqlx.toml` instead of its SQL, by
+/// naming the query with a `#[name = "..."]` hint and adding a `[macros.queries]` section
+/// for it:
+///
+/// ```toml
+/// [macros.queries.account_by_id]
+/// nullable = ["name"]
+/// non_null = ["id"]
+/// column_types = { id = "AccountId" }
+/// ```
+///
+/// ```rust,ignore
+/// // same as `select id as "id!: AccountId", name as "name?" from accounts where id = ?`
+/// let account = sqlx::query!(#[name = "account_by_id"] "select id, name from accounts where id = ?", 1i32)
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
+/// Overrides written in the query still take precedence.
+///
+/// Hints are also accepted by [`query_as!`][`crate::query_as!`] (before the record type) and
+/// [`query_scalar!`][`crate::query_scalar!`]. Only one hint may be given per query.
+///
 /// ## See Also
 /// * [`query_as!`][`crate::query_as!`] if you want to use a struct you can name,
 /// * [`query_file!`][`crate::query_file!`] if you want to define the SQL query out-of-line,
@@ -312,6 +483,24 @@
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query (
+    (#[name = $name:literal] $query:expr) => ({
+        $crate::sqlx_macros::expand_query!(source = $query, name = $name)
+    });
+    (#[name = $name:literal] $query:expr, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(source = $query, args = [$($args)*], name = $name)
+    });
+    (#[driver = $driver:literal] $query:expr) => ({
+        $crate::sqlx_macros::expand_query!(source = $query, driver = $driver)
+    });
+    (#[driver = $driver:literal] $query:expr, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(source = $query, args = [$($args)*], driver = $driver)
+    });
+    (#[timeout = $timeout:literal] $query:expr) => ({
+        $crate::sqlx_macros::expand_query!(source = $query, timeout = $timeout)
+    });
+    (#[timeout = $timeout:literal] $query:expr, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(source = $query, args = [$($args)*], timeout = $timeout)
+    });
     // in Rust 1.45 we can now invoke proc macros in expression position
     ($query:expr) => ({
         $crate::sqlx_macros::expand_query!(source = $query)
@@ -331,6 +520,13 @@ macro_rules! query (
 
 /// A variant of [`query!`][`crate::query!`] which does not check the input or output types. This still does parse
 /// the query to ensure it's syntactically and semantically valid for the current database.
+///
+/// ### Parameter Count
+/// This macro and the other `_unchecked` variants also don't check that the number of arguments
+/// matches the number of bind parameters in the query. **A mismatch is only caught at runtime**,
+/// where it is either an error from the database or, for SQLite, a missing argument is bound as
+/// `NULL`. With `assert_param_count = true` in the `[macros]` section of `sqlx.toml`, the count
+/// is checked at compile time by scanning the query text instead.
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_unchecked (
@@ -353,6 +549,10 @@ macro_rules! query_unchecked (
 /// unlike `include_str!()` which uses compiler internals to get the path of the file where it
 /// was invoked.
 ///
+/// The expansion includes the file with `include_str!()`, so editing it triggers a rebuild.
+/// As with inline queries, [offline mode](crate::query!#offline-mode) caches the query by its
+/// contents, not the path, so moving or renaming the file doesn't invalidate the cached data.
+///
 /// -----
 ///
 /// `examples/queries/account-by-id.sql`:
@@ -515,6 +715,59 @@ macro_rules! query_file_unchecked (
 /// assert_eq!(record.id, MyInt4(1));
 /// ```
 ///
+/// ### Ignoring Extra Columns
+/// Normally every column the query returns must be a field of the struct. To map only the first
+/// `N` columns and ignore the rest, precede the struct with a `#[columns = N]` hint, e.g. to
+/// reuse a query that returns more than one struct needs:
+///
+/// ```rust,ignore
+/// struct Account {
+///     id: i32,
+///     name: String,
+/// }
+///
+/// // `created_at` is not decoded
+/// let account = sqlx::query_as!(
+///     #[columns = 2]
+///     Account,
+///     "select id, name, created_at from accounts where id = ?",
+///     1i32
+/// )
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
+/// The fields are still assigned by the column names, so put the columns to keep first.
+/// The hint is also supported by [`query_as_unchecked!`][`crate::query_as_unchecked!`].
+///
+/// ### Defaulting Missing Fields
+/// Conversely, to leave fields of the struct that the query doesn't return at their default values,
+/// precede the struct with a `#[default]` hint. The struct must implement `Default`, since the macro
+/// can't see its definition to tell which fields are missing; any field that isn't a column is
+/// taken from `Default::default()`:
+///
+/// ```rust,ignore
+/// #[derive(Default)]
+/// struct Account {
+///     id: i32,
+///     name: String,
+///     // not a column yet
+///     nickname: Option<String>,
+/// }
+///
+/// let account = sqlx::query_as!(
+///     #[default]
+///     Account,
+///     "select id, name from accounts where id = ?",
+///     1i32
+/// )
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
+/// Without the hint, a field that isn't a column is a compile error, as usual.
+/// The hint is also supported by [`query_as_unchecked!`][`crate::query_as_unchecked!`].
+///
 /// ### Troubleshooting: "error: mismatched types"
 /// If you get a "mismatched types" error from an invocation of this macro and the error
 /// isn't pointing specifically at a parameter.
@@ -569,6 +822,36 @@ macro_rules! query_file_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_as (
+    (#[columns = $columns:literal] $out_struct:path, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, columns = $columns)
+    });
+    (#[columns = $columns:literal] $out_struct:path, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, args = [$($args)*], columns = $columns)
+    });
+    (#[default] $out_struct:path, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, default = true)
+    });
+    (#[default] $out_struct:path, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, args = [$($args)*], default = true)
+    });
+    (#[name = $name:literal] $out_struct:path, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, name = $name)
+    });
+    (#[name = $name:literal] $out_struct:path, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, args = [$($args)*], name = $name)
+    });
+    (#[driver = $driver:literal] $out_struct:path, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, driver = $driver)
+    });
+    (#[driver = $driver:literal] $out_struct:path, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, args = [$($args)*], driver = $driver)
+    });
+    (#[timeout = $timeout:literal] $out_struct:path, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, timeout = $timeout)
+    });
+    (#[timeout = $timeout:literal] $out_struct:path, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, args = [$($args)*], timeout = $timeout)
+    });
     ($out_struct:path, $query:expr) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query)
     });
@@ -626,6 +909,22 @@ macro_rules! query_file_as (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_as_unchecked (
+    (#[columns = $columns:literal] $out_struct:path, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, checked = false, columns = $columns)
+    });
+
+    (#[columns = $columns:literal] $out_struct:path, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, args = [$($args)*], checked = false, columns = $columns)
+    });
+
+    (#[default] $out_struct:path, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, checked = false, default = true)
+    });
+
+    (#[default] $out_struct:path, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, args = [$($args)*], checked = false, default = true)
+    });
+
     ($out_struct:path, $query:expr) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, checked = false)
     });
@@ -667,6 +966,24 @@ macro_rules! query_file_as_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_scalar (
+    (#[name = $name:literal] $query:expr) => (
+        $crate::sqlx_macros::expand_query!(scalar = _, source = $query, name = $name)
+    );
+    (#[name = $name:literal] $query:expr, $($args:tt)*) => (
+        $crate::sqlx_macros::expand_query!(scalar = _, source = $query, args = [$($args)*], name = $name)
+    );
+    (#[driver = $driver:literal] $query:expr) => (
+        $crate::sqlx_macros::expand_query!(scalar = _, source = $query, driver = $driver)
+    );
+    (#[driver = $driver:literal] $query:expr, $($args:tt)*) => (
+        $crate::sqlx_macros::expand_query!(scalar = _, source = $query, args = [$($args)*], driver = $driver)
+    );
+    (#[timeout = $timeout:literal] $query:expr) => (
+        $crate::sqlx_macros::expand_query!(scalar = _, source = $query, timeout = $timeout)
+    );
+    (#[timeout = $timeout:literal] $query:expr, $($args:tt)*) => (
+        $crate::sqlx_macros::expand_query!(scalar = _, source = $query, args = [$($args)*], timeout = $timeout)
+    );
     ($query:expr) => (
         $crate::sqlx_macros::expand_query!(scalar = _, source = $query)
     );
@@ -675,6 +992,32 @@ macro_rules! query_scalar (
     )
 );
 
+/// A variant of [`query!`][`crate::query!`] which evaluates to tuples of the columns, in order,
+/// instead of an anonymous record struct.
+///
+/// This is handy for queries with a couple of columns that are used right away, where naming
+/// the fields doesn't buy much:
+///
+/// ```rust,ignore
+/// let (id, name) = sqlx::query_tuple!("SELECT id, name FROM users WHERE id = $1", id)
+///     .fetch_one(&pool)
+///     .await?;
+/// ```
+///
+/// Nullability and type overrides in column names work as for [`query!`][`crate::query!`].
+/// Wildcard overrides aren't supported since the elements of the tuple can't be inferred;
+/// use [`query_as!`][`crate::query_as!`] with a struct for those.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_tuple (
+    ($query:expr) => (
+        $crate::sqlx_macros::expand_query!(tuple = _, source = $query)
+    );
+    ($query:expr, $($args:tt)*) => (
+        $crate::sqlx_macros::expand_query!(tuple = _, source = $query, args = [$($args)*])
+    )
+);
+
 /// A variant of [`query_scalar!`][`crate::query_scalar!`] which takes a file path like
 /// [`query_file!`][`crate::query_file!`].
 #[macro_export]
@@ -743,6 +1086,10 @@ macro_rules! query_file_scalar_unchecked (
 /// unlike `include_str!()` which uses compiler internals to get the path of the file where it
 /// was invoked.
 ///
+/// Without an argument, the directory is read from `migrate.migrations_dir` in `sqlx.toml`,
+/// if set. The [table name][crate::migrate::Migrator::set_table_name] is read from
+/// `migrate.table_name`.
+///
 /// See [MigrationSource][crate::migrate::MigrationSource] for details on structure of the ./migrations directory.
 ///
 /// ## Triggering Recompilation on Migration Changes
@@ -814,6 +1161,6 @@ macro_rules! migrate {
     }};
 
     () => {{
-        $crate::sqlx_macros::migrate!("./migrations")
+        $crate::sqlx_macros::migrate!()
     }};
 }
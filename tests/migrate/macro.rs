@@ -22,6 +22,7 @@ async fn same_output() -> anyhow::Result<()> {
 
 fn assert_same(embedded: &Migrator, runtime: &Migrator) {
     assert_eq!(runtime.migrations.len(), embedded.migrations.len());
+    assert_eq!(runtime.table_name(), embedded.table_name());
 
     for (e, r) in embedded.iter().zip(runtime.iter()) {
         assert_eq!(e.version, r.version);
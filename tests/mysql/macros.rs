@@ -197,7 +197,7 @@ async fn with_test_row<'a>(
     Ok((transaction, MyInt(id as i64)))
 }
 
-#[derive(PartialEq, Eq, Debug, sqlx::Type)]
+#[derive(PartialEq, Eq, Debug, Clone, sqlx::Type)]
 #[sqlx(transparent)]
 struct MyInt(i64);
 
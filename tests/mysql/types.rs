@@ -28,6 +28,8 @@ test_type!(i32(MySql, "2141512" == 2141512_i32));
 test_type!(u64(MySql, "CAST(2141512 AS UNSIGNED)" == 2141512_u64));
 test_type!(i64(MySql, "2141512" == 2141512_i64));
 
+test_type!(boxed_i64<Box<i64>>(MySql, "2141512" == Box::new(2141512_i64)));
+
 test_type!(f64(MySql, "3.14159265e0" == 3.14159265_f64));
 
 // NOTE: This behavior can be very surprising. MySQL implicitly widens FLOAT bind parameters
@@ -20,6 +20,134 @@ async fn test_query() -> anyhow::Result<()> {
     Ok(())
 }
 
+struct AccountKey {
+    id: i32,
+    name: Option<String>,
+}
+
+#[sqlx_macros::test]
+async fn test_query_params_struct() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // `enable_params_struct` and `enable_named_params` are set in `sqlx.toml`
+    let keys = [
+        AccountKey {
+            id: 1,
+            name: Some("Herp Derpinson".to_string()),
+        },
+        AccountKey { id: 1, name: None },
+    ];
+
+    for key in &keys {
+        let account = sqlx::query!(
+            "SELECT * from (VALUES (1, 'Herp Derpinson')) accounts(id, name) \
+             where id = $id and ($name::text is null or name = $name)",
+            params = key
+        )
+        .fetch_one(&mut conn)
+        .await?;
+
+        assert_eq!(account.id, Some(1));
+        assert_eq!(account.name.as_deref(), Some("Herp Derpinson"));
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_describe_fallback() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // inferring nullability fails for a `NOT NULL` domain parameter,
+    // so this is described by the `describe_fallback` set in `sqlx.toml`
+    let row = sqlx::query!("SELECT $1::positive_id::int4 + 1 AS next_id", 41i32 as _)
+        .fetch_one(&mut conn)
+        .await?;
+
+    // nullability is unknown in the fallback
+    assert_eq!(row.next_id, Some(42));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_unchecked_param_count_assertion() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // `assert_param_count` in `sqlx.toml` checks the two arguments against `$1` and `$2`,
+    // but not the `$3` in the string literal or the comment
+    let row = sqlx::query_unchecked!(
+        "SELECT $1::int4 + $2::int4 AS sum, '$3' AS text -- $4",
+        1i32,
+        2i32
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(row.sum, Some(3));
+    assert_eq!(row.text.as_deref(), Some("$3"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_record_key_columns() -> anyhow::Result<()> {
+    use std::collections::HashSet;
+
+    let mut conn = new::<Postgres>().await?;
+
+    // `row_key` is listed in `record_key_columns` in `sqlx.toml`
+    let rows = sqlx::query!(
+        r#"SELECT row_key "row_key!", name "name!", score "score!"
+           FROM (VALUES (1, 'Alice', 1.5::float8), (1, 'Bob', 2.5), (2, 'Alice', 1.5))
+             t(row_key, name, score)"#
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    // only the key is compared
+    assert!(rows[0] == rows[1]);
+    assert!(rows[0] != rows[2]);
+
+    let unique: HashSet<_> = rows.iter().map(|row| row.row_key).collect();
+    let deduped: HashSet<_> = rows.into_iter().collect();
+
+    assert_eq!(deduped.len(), unique.len());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_mock_describe() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // `mock_only_books` doesn't exist when building, so this query can only be expanded
+    // from `tests/postgres/mock_describe.json`
+    sqlx::query("CREATE TEMPORARY TABLE mock_only_books (id BIGINT PRIMARY KEY, title TEXT)")
+        .execute(&mut conn)
+        .await?;
+
+    sqlx::query("INSERT INTO mock_only_books (id, title) VALUES (1, 'Dune'), (2, NULL)")
+        .execute(&mut conn)
+        .await?;
+
+    let book = sqlx::query!("SELECT id, title FROM mock_only_books WHERE id = $1", 1_i64)
+        .fetch_one(&mut conn)
+        .await?;
+
+    let (id, title): (i64, Option<String>) = (book.id, book.title);
+    assert_eq!(id, 1);
+    assert_eq!(title.as_deref(), Some("Dune"));
+
+    let book = sqlx::query!("SELECT id, title FROM mock_only_books WHERE id = $1", 2_i64)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(book.title, None);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_non_null() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -124,6 +252,36 @@ async fn test_query_file() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_query_named_params() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // `enable_named_params` is set in `sqlx.toml`
+    let id = 1_i32;
+    let limit = 2_i64;
+
+    let ids = sqlx::query_scalar!(
+        r#"SELECT x as "x!" FROM generate_series($id::int4, $id + 5) x LIMIT $limit"#,
+        limit,
+        id
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(ids, [1, 2]);
+
+    let name = "Herp Derpinson";
+
+    let account = sqlx::query_file!("tests/postgres/test-named-params.sql", id, name)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(account.id, 1);
+    assert_eq!(account.name.as_deref(), Some(name));
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct Account {
     id: i32,
@@ -189,6 +347,95 @@ async fn test_query_file_as() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_query_tuple() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let (id, text, maybe): (i32, String, Option<i64>) = sqlx::query_tuple!(
+        r#"select $1::int4 as "id!", 'foo' as "text!", null::int8 as maybe"#,
+        5_i32
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(id, 5);
+    assert_eq!(text, "foo");
+    assert_eq!(maybe, None);
+
+    let row = sqlx::query_tuple!(r#"select 1::int8 as "one: i64""#)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(row.0, Some(1));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_nullability_hint() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // expressions are inferred to be nullable in Postgres
+    let (id, name, other): (i32, Option<String>, Option<String>) = sqlx::query_tuple!(
+        r#"
+        /* sqlx:not-null */
+        /* sqlx:null=name */
+        select 1::int4 as id, 'foo' as name, 'bar' as "other?"
+        "#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(id, 1);
+    assert_eq!(name.as_deref(), Some("foo"));
+    assert_eq!(other.as_deref(), Some("bar"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_file_nullability_hint() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // `current_query()` is the query text as the database received it
+    let record = sqlx::query_file!("tests/postgres/test-hint-query.sql")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let query: String = record.query;
+    assert!(query.contains("current_query()"), "{query}");
+    assert!(!query.contains("sqlx:"), "{query}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_type_hint_comments() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let record = sqlx::query!(
+        r#"
+        select 1::int4 as "id!", -- sqlx:type=u8
+               null::int8 as total -- sqlx:type=u64
+        "#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    let id: u8 = record.id;
+    let total: Option<u64> = record.total;
+
+    assert_eq!(id, 1);
+    assert_eq!(total, None);
+
+    let count: u16 = sqlx::query_scalar!(r#"select 300::int4 as "count!" -- sqlx:type=u16"#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 300);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_query_scalar() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -203,6 +450,23 @@ async fn test_query_scalar() -> anyhow::Result<()> {
         .await?;
     assert_eq!(id, Some(1i32));
 
+    let id = sqlx::query_scalar!(r#"/* sqlx:not-null */ select 1 as "&foo""#)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(id, 1i32);
+
+    // an aggregate is `NULL` without any rows
+    let max: Option<i64> = sqlx::query_scalar!("select max(id) from tweet where false")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(max, None);
+
+    // `Option<i64>` for no rows, not `Option<Option<i64>>`, since `id` is `NOT NULL`
+    let id: Option<i64> = sqlx::query_scalar!("select id from tweet where false")
+        .fetch_optional(&mut conn)
+        .await?;
+    assert_eq!(id, None);
+
     let id = sqlx::query_scalar!(r#"select 1 as "foo!""#)
         .fetch_one(&mut conn)
         .await?;
@@ -443,11 +707,45 @@ async fn with_test_row<'a>(
     Ok(transaction)
 }
 
-#[derive(PartialEq, Eq, Debug, sqlx::Type)]
+#[derive(PartialEq, Eq, Debug, Clone, sqlx::Type)]
+#[sqlx(type_name = "status", rename_all = "lowercase")]
+enum Status {
+    New,
+    Open,
+    Closed,
+}
+
+#[sqlx_macros::test]
+async fn test_type_overrides() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // `citext` and `status` are mapped in `[macros.type_overrides]` in `sqlx.toml`
+    let row = sqlx::query!(
+        r#"SELECT 'Hello'::citext "text!", 'open'::status "status!", NULL::status "nothing""#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    let text: String = row.text;
+    assert_eq!(text, "Hello");
+    assert_eq!(row.status, Status::Open);
+    assert_eq!(row.nothing, None);
+
+    // an override in the query takes precedence
+    let text = sqlx::query_scalar!(r#"SELECT 'Hello'::citext "text!: Box<str>""#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(text, Box::from("Hello"));
+
+    Ok(())
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, sqlx::Type)]
 #[sqlx(transparent)]
 struct MyInt(i64);
 
-#[derive(PartialEq, Eq, Debug, sqlx::Type)]
+#[derive(PartialEq, Eq, Debug, Clone, sqlx::Type)]
 #[sqlx(transparent)]
 struct MyInt4(i32);
 
@@ -459,6 +757,127 @@ struct OptionalRecord {
     id: Option<MyInt>,
 }
 
+#[sqlx_macros::test]
+async fn test_force_nullable() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut conn = with_test_row(&mut conn).await?;
+
+    // `tweet.id` is `NOT NULL`, but these columns are listed in `sqlx.toml`
+    let row = sqlx::query!(r#"SELECT id "forced_nullable_id", id FROM tweet WHERE id = 1"#)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    let forced: Option<i64> = row.forced_nullable_id;
+    let id: i64 = row.id;
+    assert_eq!(forced, Some(id));
+
+    let row = sqlx::query!(
+        r#"SELECT id "per_query_id" FROM tweet WHERE id = $1"#,
+        1_i64
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let forced: Option<i64> = row.per_query_id;
+    assert_eq!(forced, Some(1));
+
+    // only that query is affected
+    let row = sqlx::query!(r#"SELECT id "per_query_id" FROM tweet"#)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    let id: i64 = row.per_query_id;
+    assert_eq!(id, 1);
+
+    // `!` still takes precedence
+    let id: i64 = sqlx::query_scalar!(r#"SELECT id "forced_nullable_id!" FROM tweet"#)
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(id, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_as_ignore_extra_columns() -> anyhow::Result<()> {
+    struct Account {
+        id: i32,
+        name: String,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let account = sqlx::query_as!(
+        #[columns = 2]
+        Account,
+        r#"SELECT id "id!", name "name!", 'ignored' "extra", 1.5::float8 "extra_2"
+           FROM (VALUES (1, 'Alice')) accounts(id, name)
+           WHERE id = $1"#,
+        1
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(account.id, 1);
+    assert_eq!(account.name, "Alice");
+
+    let account = sqlx::query_as_unchecked!(
+        #[columns = 2]
+        Account,
+        r#"SELECT 2 "id!", 'Bob' "name!", NULL "extra""#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(account.id, 2);
+    assert_eq!(account.name, "Bob");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_as_default_fields() -> anyhow::Result<()> {
+    #[derive(Default)]
+    struct Account {
+        id: i32,
+        name: String,
+        nickname: Option<String>,
+        logins: i64,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let account = sqlx::query_as!(
+        #[default]
+        Account,
+        r#"SELECT id "id!", name "name!"
+           FROM (VALUES (1, 'Alice')) accounts(id, name)
+           WHERE id = $1"#,
+        1
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(account.id, 1);
+    assert_eq!(account.name, "Alice");
+    assert_eq!(account.nickname, None);
+    assert_eq!(account.logins, 0);
+
+    let account = sqlx::query_as_unchecked!(
+        #[default]
+        Account,
+        r#"SELECT 2 "id!", 'Bob' "name!", 3::int8 "logins!""#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(account.id, 2);
+    assert_eq!(account.name, "Bob");
+    assert_eq!(account.logins, 3);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_column_override_wildcard() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -636,3 +1055,72 @@ async fn test_to_from_citext() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn test_driver_hint() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // `postgres` is mapped to `DATABASE_URL` in `sqlx.toml`
+    let row = sqlx::query!(
+        #[driver = "postgres"]
+        "SELECT $1::int4 as id",
+        1_i32
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!(row.id, Some(1));
+
+    let account = sqlx::query_as!(
+        #[driver = "postgres"]
+        Account,
+        r#"SELECT 1 AS "id!", 'Herp Derpinson' AS "name""#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!(account.id, 1);
+
+    let id = sqlx::query_scalar!(
+        #[driver = "postgres"]
+        r#"SELECT 2::int4 AS "id!""#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!(id, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_named_query() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut conn = with_test_row(&mut conn).await?;
+
+    // the overrides are in `[macros.queries.named_tweet]` in `sqlx.toml`
+    let row = sqlx::query!(
+        #[name = "named_tweet"]
+        "SELECT id, text, 1::int4 AS one FROM tweet WHERE id = 1"
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let id: Option<i64> = row.id;
+    let text: Box<str> = row.text;
+    let one: i32 = row.one;
+
+    assert_eq!(id, Some(1));
+    assert_eq!(&*text, "#sqlx is pretty cool!");
+    assert_eq!(one, 1);
+
+    // an override in the query takes precedence
+    let row = sqlx::query!(
+        #[name = "named_tweet"]
+        r#"SELECT id "id!", text, 1::int4 AS one FROM tweet WHERE id = 1"#
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let id: i64 = row.id;
+    assert_eq!(id, 1);
+
+    Ok(())
+}
@@ -27,6 +27,131 @@ async fn it_connects() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_times_out_slow_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("SET statement_timeout = '10min'").await?;
+
+    let res = sqlx::query("SELECT pg_sleep(1)")
+        .timeout(Duration::from_millis(50))
+        .execute(&mut conn)
+        .await;
+
+    assert!(
+        matches!(res, Err(sqlx::Error::QueryTimedOut(t)) if t == Duration::from_millis(50)),
+        "{res:?}"
+    );
+
+    // the server cancelled the statement and the previous setting is back
+    let setting: String = sqlx::query_scalar("SHOW statement_timeout")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(setting, "10min");
+
+    // the same holds in a transaction that keeps going after a query with a timeout
+    let mut tx = conn.begin().await?;
+
+    let value: i32 = sqlx::query_scalar("SELECT 1")
+        .timeout(Duration::from_secs(5))
+        .fetch_one(&mut *tx)
+        .await?;
+
+    assert_eq!(value, 1);
+
+    let setting: String = sqlx::query_scalar("SHOW statement_timeout")
+        .fetch_one(&mut *tx)
+        .await?;
+
+    assert_eq!(setting, "10min");
+
+    tx.rollback().await?;
+
+    // the connection is still usable afterwards
+    let value: i32 = sqlx::query_scalar("SELECT 1")
+        .timeout(Duration::from_secs(5))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_typed_decode_errors() -> anyhow::Result<()> {
+    use sqlx::error::DecodeError;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let row = conn.fetch_one("SELECT NULL::int4, 1::int4").await?;
+
+    let err = row.try_get::<i32, _>(0).unwrap_err();
+
+    assert!(
+        matches!(
+            &err,
+            sqlx::Error::ColumnDecode { source, .. }
+                if matches!(source.downcast_ref(), Some(DecodeError::UnexpectedNull))
+        ),
+        "{err:?}"
+    );
+
+    let err = row.try_get::<String, _>(1).unwrap_err();
+
+    let sqlx::Error::ColumnDecode { source, .. } = &err else {
+        panic!("expected `Error::ColumnDecode`, got {err:?}");
+    };
+
+    match source.downcast_ref() {
+        Some(DecodeError::TypeMismatch { expected, got, .. }) => {
+            assert_eq!(expected, "TEXT");
+            assert_eq!(got, "INT4");
+        }
+        _ => panic!("expected `DecodeError::TypeMismatch`, got {source:?}"),
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_large_objects_in_chunks() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgLargeObject;
+
+    let mut conn = new::<Postgres>().await?;
+
+    // creating the object is rolled back with the transaction
+    let mut tx = conn.begin().await?;
+
+    let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+
+    let blob: PgLargeObject = sqlx::query_scalar("SELECT lo_from_bytea(0, $1)")
+        .bind(&data)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    let mut reader = blob.open(&mut *tx).await?;
+
+    let mut chunks = Vec::new();
+
+    while let Some(chunk) = reader.read_chunk(300).await? {
+        chunks.push(chunk);
+    }
+
+    reader.close().await?;
+
+    assert_eq!(
+        chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+        [300, 300, 300, 100]
+    );
+    assert_eq!(chunks.concat(), data);
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_select_void() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -58,3 +58,7 @@ CREATE TABLE test_citext (
 CREATE SCHEMA IF NOT EXISTS foo;
 
 CREATE TYPE foo."Foo" as ENUM ('Bar', 'Baz');
+
+-- `EXPLAIN EXECUTE` with a `NULL` argument fails for parameters of this type,
+-- so queries taking one can only be described by the `describe_fallback`.
+CREATE DOMAIN positive_id AS INT4 NOT NULL CHECK (VALUE > 0);
@@ -0,0 +1,2 @@
+/* sqlx:not-null */
+SELECT current_query() AS query
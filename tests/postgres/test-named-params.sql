@@ -0,0 +1 @@
+SELECT id "id!", name from (VALUES (1, $name::text)) accounts(id, name) where id = $id
@@ -8,7 +8,8 @@ use sqlx::postgres::Postgres;
 use sqlx_test::{new, test_decode_type, test_prepared_type, test_type};
 
 use sqlx_core::executor::Executor;
-use sqlx_core::types::Text;
+use sqlx_core::row::Row;
+use sqlx_core::types::{Narrow, Text};
 use std::str::FromStr;
 
 test_type!(null<Option<i16>>(Postgres,
@@ -124,8 +125,30 @@ test_type!(i32_array<[i32; 4]>(Postgres,
     "'{5,10,50,100}'::int[]" == [5_i32, 10, 50, 100],
 ));
 
+test_type!(f32_array<[f32; 3]>(Postgres,
+    "'{0.5,1.5,-2}'::real[]" == [0.5_f32, 1.5, -2.0],
+));
+
+#[sqlx_macros::test]
+async fn test_array_wrong_length() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let row = conn.fetch_one("SELECT '{1,2}'::real[]").await?;
+
+    let err = row.try_get::<[f32; 3], _>(0).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("wrong number of elements: expected an array of 3, got 2"),
+        "{err}"
+    );
+
+    Ok(())
+}
+
 test_type!(i64(Postgres, "9358295312::bigint" == 9358295312_i64));
 
+test_type!(boxed_i64<Box<i64>>(Postgres, "9358295312::bigint" == Box::new(9358295312_i64)));
+
 test_type!(f32(Postgres, "9419.122::real" == 9419.122_f32));
 
 test_type!(f64(
@@ -199,6 +222,51 @@ test_type!(ipnetwork<sqlx::types::ipnetwork::IpNetwork>(Postgres,
             .unwrap(),
 ));
 
+#[cfg(feature = "ipnetwork")]
+test_type!(ipaddr<std::net::IpAddr>(Postgres,
+    "'127.0.0.1'::inet" == "127.0.0.1".parse::<std::net::IpAddr>().unwrap(),
+    "'::ffff:1.2.3.0'::inet" == "::ffff:1.2.3.0".parse::<std::net::IpAddr>().unwrap(),
+));
+
+#[cfg(feature = "ipnetwork")]
+test_type!(ipv4addr<std::net::Ipv4Addr>(Postgres,
+    "'8.8.8.8'::inet" == std::net::Ipv4Addr::new(8, 8, 8, 8),
+));
+
+#[cfg(feature = "ipnetwork")]
+test_type!(ipv6addr<std::net::Ipv6Addr>(Postgres,
+    "'2001:4f8:3:ba::1'::inet" == "2001:4f8:3:ba::1".parse::<std::net::Ipv6Addr>().unwrap(),
+));
+
+#[cfg(feature = "ipnetwork")]
+test_decode_type!(ipaddr_text<std::net::IpAddr>(Postgres,
+    "'127.0.0.1'::text" == "127.0.0.1".parse::<std::net::IpAddr>().unwrap(),
+    "'2001:4f8:3:ba::1'::text" == "2001:4f8:3:ba::1".parse::<std::net::IpAddr>().unwrap(),
+));
+
+#[cfg(feature = "ipnetwork")]
+#[sqlx_macros::test]
+async fn test_ipv4addr_from_ipv6() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let row = conn.fetch_one("SELECT '::1'::inet").await?;
+
+    let err = row.try_get::<std::net::Ipv4Addr, _>(0).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("expected an IPv4 address, got ::1"),
+        "{err}"
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "url")]
+test_type!(url<sqlx::types::Url>(Postgres,
+    "'https://example.com/path?query=1'::text"
+        == sqlx::types::Url::parse("https://example.com/path?query=1").unwrap(),
+));
+
 #[cfg(feature = "mac_address")]
 test_type!(mac_address<sqlx::types::mac_address::MacAddress>(Postgres,
     "'00:01:02:03:04:05'::macaddr"
@@ -358,6 +426,7 @@ mod json {
     use sqlx::types::Json;
     use sqlx::{Executor, Row};
     use sqlx_test::new;
+    use std::collections::HashMap;
 
     // When testing JSON, coerce to JSONB for `=` comparison as `JSON = JSON` is not
     // supported in PostgreSQL
@@ -396,6 +465,19 @@ mod json {
         "array['\"😎\"'::jsonb, '\"🙋‍♀️\"'::jsonb]::jsonb[]" == vec![json!("😎"), json!("🙋‍♀️")],
     ));
 
+    test_type!(json_hash_map<HashMap<String, i32>>(
+        Postgres,
+        "SELECT ({0}::jsonb is not distinct from $1::jsonb)::int4, {0} as _2, $2 as _3",
+        "'{\"a\": 1, \"b\": 2}'::json" == HashMap::from([("a".to_string(), 1), ("b".to_string(), 2)]),
+        "'{}'::json" == HashMap::<String, i32>::new(),
+    ));
+
+    test_type!(jsonb_hash_map<HashMap<String, Vec<String>>>(
+        Postgres,
+        "'{\"a\": [\"x\", \"y\"], \"b\": []}'::jsonb"
+            == HashMap::from([("a".to_string(), vec!["x".to_string(), "y".to_string()]), ("b".to_string(), vec![])]),
+    ));
+
     #[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
     struct Friend {
         name: String,
@@ -438,6 +520,42 @@ mod json {
 
         Ok(())
     }
+
+    #[sqlx_macros::test]
+    async fn test_json_boxed_raw_value() -> anyhow::Result<()> {
+        let mut conn = new::<Postgres>().await?;
+
+        // `JSON` keeps the text as written, so the value must come back byte-for-byte
+        let text = "{\"b\":  [1, 2],\n \"a\": {}}";
+
+        // unprepared, text API
+        let row: PgRow = conn.fetch_one(&*format!("SELECT '{text}'::json")).await?;
+
+        let value: Box<JsonRawValue> = row.try_get(0)?;
+
+        assert_eq!(value.get(), text);
+
+        // prepared, binary API
+        let row: PgRow = conn
+            .fetch_one(sqlx::query(&format!("SELECT '{text}'::json")))
+            .await?;
+
+        let value: Box<JsonRawValue> = row.try_get(0)?;
+
+        assert_eq!(value.get(), text);
+
+        // round-trip through a parameter, which is sent as JSONB and so normalized by Postgres
+        let raw = JsonRawValue::from_string(text.to_owned())?;
+
+        let value: Box<JsonRawValue> = sqlx::query_scalar("SELECT $1")
+            .bind(&raw)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value.get(), "{\"a\": {}, \"b\": [1, 2]}");
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "bigdecimal")]
@@ -492,6 +610,40 @@ test_type!(_cube<Vec<sqlx::postgres::types::PgCube>>(Postgres,
     "array[cube(2.2,-3.4)]" == vec![sqlx::postgres::types::PgCube::OneDimensionInterval(2.2, -3.4)],
 ));
 
+// `point` has no equality operator, so only decoding can be tested with these macros
+test_decode_type!(point<sqlx::postgres::types::PgPoint>(Postgres,
+    "point(2.5, 3)" == sqlx::postgres::types::PgPoint { x: 2.5, y: 3. },
+    "point(-2.5, -3.125)" == sqlx::postgres::types::PgPoint { x: -2.5, y: -3.125 },
+    "point(1e20, -4.5e-7)" == sqlx::postgres::types::PgPoint { x: 1e20, y: -4.5e-7 },
+    "point(0, 0)" == sqlx::postgres::types::PgPoint { x: 0., y: 0. },
+));
+
+test_decode_type!(_point<Vec<sqlx::postgres::types::PgPoint>>(Postgres,
+    "array[point(1, 2), point(-3e-5, 4)]" == vec![
+        sqlx::postgres::types::PgPoint { x: 1., y: 2. },
+        sqlx::postgres::types::PgPoint { x: -3e-5, y: 4. },
+    ],
+));
+
+#[sqlx_macros::test]
+async fn test_encode_point() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgPoint;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let point = PgPoint { x: -1.5, y: 2e-10 };
+
+    let (same, decoded): (bool, PgPoint) = sqlx::query_as("SELECT $1 ~= point(-1.5, 2e-10), $1")
+        .bind(point)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(same);
+    assert_eq!(decoded, point);
+
+    Ok(())
+}
+
 #[cfg(feature = "rust_decimal")]
 test_type!(decimal<sqlx::types::Decimal>(Postgres,
     "0::numeric" == sqlx::types::Decimal::from_str("0").unwrap(),
@@ -565,6 +717,32 @@ test_prepared_type!(interval<PgInterval>(
         },
 ));
 
+test_prepared_type!(std_duration<std::time::Duration>(
+    Postgres,
+    "INTERVAL '1h'" == std::time::Duration::from_secs(3_600),
+    "INTERVAL '03:10:20.116100'"
+        == std::time::Duration::from_micros((3 * 3_600 + 10 * 60 + 20) * 1_000_000 + 116100),
+    "INTERVAL '1 day 2 hours'" == std::time::Duration::from_secs(26 * 3_600),
+));
+
+#[sqlx_macros::test]
+async fn test_std_duration_invalid() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // `INTERVAL` is only supported in binary format, i.e. for prepared queries
+    let row = sqlx::query("SELECT INTERVAL '-1 hours', INTERVAL '1 month'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let err = row.try_get::<std::time::Duration, _>(0).unwrap_err();
+    assert!(err.to_string().contains("non-negative"), "{err}");
+
+    let err = row.try_get::<std::time::Duration, _>(1).unwrap_err();
+    assert!(err.to_string().contains("months"), "{err}");
+
+    Ok(())
+}
+
 test_prepared_type!(money<PgMoney>(Postgres, "123.45::money" == PgMoney(12345)));
 
 test_prepared_type!(money_vec<Vec<PgMoney>>(Postgres,
@@ -640,3 +818,33 @@ CREATE TEMPORARY TABLE user_login (
 
     Ok(())
 }
+
+test_decode_type!(narrow_u8<Narrow<u8>>(Postgres,
+    "0::int8" == Narrow(0_u8),
+    "255::int8" == Narrow(255_u8),
+    "42::int4" == Narrow(42_u8),
+    "7::int2" == Narrow(7_u8),
+));
+
+#[sqlx_macros::test]
+async fn test_narrow_adapter_out_of_range() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let row = conn.fetch_one("SELECT 256::int8, (-1)::int4").await?;
+
+    let err = row.try_get::<Narrow<u8>, _>(0).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("value 256 does not fit in target type u8"),
+        "{err}"
+    );
+
+    let err = row.try_get::<Narrow<u32>, _>(1).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("value -1 does not fit in target type u32"),
+        "{err}"
+    );
+
+    Ok(())
+}
@@ -16,6 +16,55 @@ async fn macro_select() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn macro_select_redacted_column() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // `api_token` is listed in `macros.redacted_columns` in `sqlx.toml`
+    let account = sqlx::query!("select id, name as api_token from accounts where id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!("Herp Derpinson", account.api_token);
+
+    let debug = format!("{account:?}");
+    assert_eq!(debug, r#"Record { id: 1, api_token: "***" }"#);
+    assert!(!debug.contains("Herp Derpinson"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn macro_select_non_exhaustive_record() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // `record_non_exhaustive` is set in `sqlx.toml`;
+    // the macro must still be able to construct the record itself
+    let account = sqlx::query!("select id, name from accounts where id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(1, account.id);
+    assert_eq!("Herp Derpinson", account.name);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn macro_select_record_derives() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // `Clone` is listed in `macros.record_derives` in `sqlx.toml`, also for redacted records
+    let account = sqlx::query!("select id, name as api_token from accounts where id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let cloned = account.clone();
+    assert_eq!(account.api_token, cloned.api_token);
+
+    Ok(())
+}
+
 macro_rules! gen_macro_select_concats {
     ($param:literal) => {
         #[sqlx_macros::test]
@@ -88,6 +137,22 @@ async fn macro_select_bind() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn macro_select_unchecked_param_count() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // the unchecked macros don't check the argument count, and SQLite binds the missing
+    // argument as `NULL`; `assert_param_count` can't count `$N` parameters for SQLite either
+    let row = sqlx::query_unchecked!(r#"select $1 as "first?: i32", $2 as "second?: i32""#, 1i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.first, Some(1));
+    assert_eq!(row.second, None);
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct RawAccount {
     id: i64,
@@ -217,7 +282,7 @@ async fn test_column_override_nullable() -> anyhow::Result<()> {
     Ok(())
 }
 
-#[derive(PartialEq, Eq, Debug, sqlx::Type)]
+#[derive(PartialEq, Eq, Debug, Clone, sqlx::Type)]
 #[sqlx(transparent)]
 struct MyInt(i64);
 
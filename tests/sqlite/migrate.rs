@@ -66,6 +66,35 @@ async fn reversible(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx::test(migrations = false)]
+async fn custom_table_name(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+    conn.execute("DROP TABLE custom_migrations").await.ok();
+
+    let mut migrator = Migrator::new(Path::new("tests/sqlite/migrations_simple")).await?;
+    migrator.set_table_name("custom_migrations");
+
+    migrator.run(&mut conn).await?;
+
+    let applied: i64 = conn
+        .fetch_one("SELECT COUNT(*) FROM custom_migrations")
+        .await?
+        .get(0);
+    assert_eq!(applied, migrator.iter().count() as i64);
+
+    let default_table: Option<String> = sqlx::query_scalar(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations'",
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    assert_eq!(default_table, None);
+
+    // running it a 2nd time should find the applied migrations in the same table
+    migrator.run(&mut conn).await?;
+
+    Ok(())
+}
+
 /// Ensure that we have a clean initial state.
 async fn clean_up(conn: &mut SqliteConnection) -> anyhow::Result<()> {
     conn.execute("DROP TABLE migrations_simple_test").await.ok();
@@ -3,9 +3,9 @@ extern crate time_ as time;
 use sqlx::sqlite::{Sqlite, SqliteRow};
 use sqlx_core::executor::Executor;
 use sqlx_core::row::Row;
-use sqlx_core::types::Text;
+use sqlx_core::types::{Narrow, Text};
 use sqlx_test::new;
-use sqlx_test::test_type;
+use sqlx_test::{test_decode_type, test_type};
 use std::net::SocketAddr;
 
 test_type!(null<Option<i32>>(Sqlite,
@@ -42,9 +42,11 @@ test_type!(bytes<Vec<u8>>(Sqlite,
 #[cfg(feature = "json")]
 mod json_tests {
     use super::*;
+    use serde_json::value::RawValue as JsonRawValue;
     use serde_json::{json, Value as JsonValue};
     use sqlx::types::Json;
     use sqlx_test::test_type;
+    use std::collections::HashMap;
 
     test_type!(json<JsonValue>(
         Sqlite,
@@ -65,6 +67,13 @@ mod json_tests {
         "\'{\"name\":\"Joe\",\"age\":33}\'" == Json(Friend { name: "Joe".to_string(), age: 33 })
     ));
 
+    test_type!(json_hash_map<HashMap<String, Friend>>(
+        Sqlite,
+        "\'{\"joe\":{\"name\":\"Joe\",\"age\":33}}\'"
+            == HashMap::from([("joe".to_string(), Friend { name: "Joe".to_string(), age: 33 })]),
+        "\'{}\'" == HashMap::<String, Friend>::new()
+    ));
+
     // NOTE: This is testing recursive (and transparent) usage of the `Json` wrapper. You don't
     //       need to wrap the Vec in Json<_> to make the example work.
 
@@ -78,6 +87,12 @@ mod json_tests {
         "\'{\"json_column\":[1,2]}\'" == Json(Customer { json_column: Json(vec![1, 2]) })
     ));
 
+    // a multi-column sub-query returned as a JSON array decodes to a tuple through `Json`
+    test_type!(json_tuple<Json<(i64, String)>>(
+        Sqlite,
+        "'[1,\"Joe\"]'" == Json((1, "Joe".to_string()))
+    ));
+
     #[sqlx_macros::test]
     async fn it_json_extracts() -> anyhow::Result<()> {
         let mut conn = new::<Sqlite>().await?;
@@ -92,6 +107,32 @@ mod json_tests {
 
         Ok(())
     }
+
+    #[sqlx_macros::test]
+    async fn it_decodes_boxed_json_raw_value() -> anyhow::Result<()> {
+        let mut conn = new::<Sqlite>().await?;
+
+        let text = "{\"b\":  [1, 2],\n \"a\": {}}";
+
+        let value: Box<JsonRawValue> = sqlx::query_scalar("SELECT ?1")
+            .bind(text)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value.get(), text);
+
+        // `TEXT` may hold anything, but a `RawValue` is always valid JSON
+        let res = sqlx::query_scalar::<_, Box<JsonRawValue>>("SELECT '{\"a\": '")
+            .fetch_one(&mut conn)
+            .await;
+
+        assert!(
+            matches!(res, Err(sqlx::Error::ColumnDecode { .. })),
+            "{res:?}"
+        );
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "chrono")]
@@ -250,3 +291,169 @@ CREATE TEMPORARY TABLE user_login (
 
     Ok(())
 }
+
+test_type!(boxed_i64<Box<i64>>(Sqlite,
+    "9358295312" == Box::new(9358295312_i64),
+));
+
+test_type!(boxed_string<Box<String>>(Sqlite,
+    "'this is foo'" == Box::new("this is foo".to_owned()),
+));
+
+test_type!(non_zero_i64<std::num::NonZeroI64>(Sqlite,
+    "9358295312" == std::num::NonZeroI64::new(9358295312).unwrap(),
+));
+
+#[sqlx_macros::test]
+async fn test_non_zero_zero() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = conn.fetch_one("SELECT 0").await?;
+
+    let err = row.try_get::<std::num::NonZeroI64, _>(0).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("value 0 does not fit in target type NonZeroI64"),
+        "{err}"
+    );
+    assert!(
+        matches!(
+            &err,
+            sqlx::Error::ColumnDecode { source, .. }
+                if matches!(
+                    source.downcast_ref(),
+                    Some(sqlx::error::DecodeError::OutOfRange { value, target: "NonZeroI64" })
+                        if value == "0"
+                )
+        ),
+        "{err:?}"
+    );
+
+    Ok(())
+}
+
+test_type!(ipaddr<std::net::IpAddr>(Sqlite,
+    "'127.0.0.1'" == "127.0.0.1".parse::<std::net::IpAddr>().unwrap(),
+    "'::ffff:1.2.3.0'" == "::ffff:1.2.3.0".parse::<std::net::IpAddr>().unwrap(),
+));
+
+test_type!(ipv4addr<std::net::Ipv4Addr>(Sqlite,
+    "'8.8.8.8'" == std::net::Ipv4Addr::new(8, 8, 8, 8),
+));
+
+test_type!(ipv6addr<std::net::Ipv6Addr>(Sqlite,
+    "'2001:4f8:3:ba::1'" == "2001:4f8:3:ba::1".parse::<std::net::Ipv6Addr>().unwrap(),
+));
+
+#[cfg(feature = "url")]
+test_type!(url<sqlx::types::Url>(Sqlite,
+    "'https://example.com/path?query=1'"
+        == sqlx::types::Url::parse("https://example.com/path?query=1").unwrap(),
+));
+
+#[cfg(feature = "url")]
+#[sqlx_macros::test]
+async fn test_url_invalid() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = conn.fetch_one("SELECT 'not a url'").await?;
+
+    let err = row.try_get::<sqlx::types::Url, _>(0).unwrap_err();
+    assert!(
+        err.to_string().contains("relative URL without a base"),
+        "{err}"
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "semver")]
+test_type!(semver_version<sqlx::types::semver::Version>(Sqlite,
+    "'1.2.3'" == sqlx::types::semver::Version::new(1, 2, 3),
+    "'1.0.0-alpha.1+build.5'"
+        == sqlx::types::semver::Version::parse("1.0.0-alpha.1+build.5").unwrap(),
+));
+
+#[cfg(feature = "semver")]
+test_type!(semver_version_req<sqlx::types::semver::VersionReq>(Sqlite,
+    "'>=1.2.3, <2.0.0'" == sqlx::types::semver::VersionReq::parse(">=1.2.3, <2.0.0").unwrap(),
+));
+
+test_type!(std_duration<std::time::Duration>(Sqlite,
+    "0" == std::time::Duration::ZERO,
+    "1500000" == std::time::Duration::from_millis(1500),
+));
+
+#[sqlx_macros::test]
+async fn test_std_duration_invalid() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = conn.fetch_one("SELECT -1").await?;
+
+    let err = row.try_get::<std::time::Duration, _>(0).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("can only decode non-negative values; got -1"),
+        "{err}"
+    );
+
+    let Err(err) = sqlx::query("SELECT ?")
+        .bind(std::time::Duration::from_nanos(1))
+        .fetch_one(&mut conn)
+        .await
+    else {
+        panic!("expected an error encoding nanoseconds");
+    };
+    assert!(err.to_string().contains("nanoseconds"), "{err}");
+
+    Ok(())
+}
+
+test_decode_type!(narrow_u8<Narrow<u8>>(Sqlite,
+    "0" == Narrow(0_u8),
+    "255" == Narrow(255_u8),
+));
+
+#[sqlx_macros::test]
+async fn test_narrow_adapter_out_of_range() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = conn.fetch_one("SELECT 256, -1").await?;
+
+    let err = row.try_get::<Narrow<u8>, _>(0).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("value 256 does not fit in target type u8"),
+        "{err}"
+    );
+
+    let err = row.try_get::<Narrow<u16>, _>(1).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("value -1 does not fit in target type u16"),
+        "{err}"
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_codec_bound() -> anyhow::Result<()> {
+    async fn round_trip<T>(value: T) -> anyhow::Result<T>
+    where
+        T: sqlx::Codec<Sqlite> + sqlx::Type<Sqlite> + Send + Unpin,
+    {
+        let mut conn = new::<Sqlite>().await?;
+
+        Ok(sqlx::query_scalar("SELECT ?")
+            .bind(value)
+            .fetch_one(&mut conn)
+            .await?)
+    }
+
+    assert_eq!(round_trip(42_i64).await?, 42);
+    assert_eq!(round_trip("hello".to_string()).await?, "hello");
+    assert_eq!(round_trip(Some(1.5_f64)).await?, Some(1.5));
+
+    Ok(())
+}